@@ -4,7 +4,7 @@ use graph::prelude::*;
 use rusty_graphs::Package;
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
 
     let started = Instant::now();
 
@@ -3,15 +3,16 @@
 use std::time::Instant;
 
 use graphific::{AnyGraph, BasicUndirectedGraph, Vertex};
+use rusty_graphs::CityGraph;
 
 fn main() {
-    let cities = rusty_graphs::load_cities();
+    let input: CityGraph = CityGraph::new(rusty_graphs::load_cities().unwrap());
 
     let mut graph = BasicUndirectedGraph::new();
 
     let started = Instant::now();
 
-    for (key, _) in cities.iter().enumerate() {
+    for (key, _) in input.cities.iter().enumerate() {
         let v = Vertex::with_value(key, ());
         graph = graph.add_vertex(v).unwrap();
     }
@@ -21,17 +22,8 @@ fn main() {
 
     let started = Instant::now();
 
-    for i in 0..cities.len() {
-        let src = &cities[i];
-
-        #[allow(clippy::needless_range_loop)]
-        for j in (i + 1)..cities.len() {
-            let dst = &cities[j];
-
-            if src.are_connected(dst).is_some() {
-                graph = graph.add_edge_between_keys(i, j).unwrap();
-            }
-        }
+    for &(i, j, _) in input.edges.iter() {
+        graph = graph.add_edge_between_keys(i, j).unwrap();
     }
 
     println!("connecting vertices took {:?}", started.elapsed());
@@ -42,17 +34,17 @@ fn main() {
     let (start, target) = graph
         .vertices()
         .into_iter()
-        .fold((0, 0), |(start, target), v| {
-            let city = &cities[*v.key()];
-            if city.name == rusty_graphs::DIJKSTRA_START {
-                (*v.key(), target)
-            } else if city.name == rusty_graphs::DIJKSTRA_TARGET {
-                (start, *v.key())
-            } else {
-                (start, target)
-            }
+        .fold((None, None), |(s, t), v| {
+            let name = &input.cities[*v.key()].name;
+            (
+                s.or((*name == rusty_graphs::DIJKSTRA_START).then_some(*v.key())),
+                t.or((*name == rusty_graphs::DIJKSTRA_TARGET).then_some(*v.key())),
+            )
         });
 
+    let start = start.expect("start city not found");
+    let target = target.expect("target city not found");
+
     println!("finding start and target took {:?}", started.elapsed());
 
     println!("dijkstra (without goal) not available");
@@ -3,7 +3,7 @@ use std::time::Instant;
 use graphific::{AnyGraph, BasicDirectedGraph, Vertex};
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
 
     let mut graph = BasicDirectedGraph::new();
 
@@ -1,18 +1,18 @@
 use std::time::Instant;
 
-use rusty_graphs::City;
+use rusty_graphs::{City, CityGraph};
 
-use graphlib::{iterators::Dijkstra, Graph, VertexId};
+use graphlib::{iterators::Dijkstra, Graph};
 
 fn main() {
-    let cities = rusty_graphs::load_cities();
+    let input: CityGraph = CityGraph::new(rusty_graphs::load_cities().unwrap());
 
     let mut graph = Graph::new();
-    let mut vertices = Vec::with_capacity(cities.len());
+    let mut vertices = Vec::with_capacity(input.cities.len());
 
     let started = Instant::now();
 
-    for city in cities.iter() {
+    for city in input.cities.iter() {
         let v = graph.add_vertex(city);
         vertices.push(v);
     }
@@ -22,24 +22,17 @@ fn main() {
 
     let started = Instant::now();
 
-    for i in 0..graph.vertex_count() {
-        for j in (i + 1)..graph.vertex_count() {
-            let i = &vertices[i];
-            let j = &vertices[j];
+    for &(i, j, edge) in input.edges.iter() {
+        let i = &vertices[i];
+        let j = &vertices[j];
 
-            let src = graph.fetch(i).unwrap();
-            let dst = graph.fetch(j).unwrap();
+        // Weights are required to be in range [0, 1].
+        let edge_norm = edge / City::MAX_DISTANCE;
 
-            if let Some(edge) = src.are_connected(dst) {
-                // Weights are required to be in range [0, 1].
-                let edge_norm = edge / City::MAX_DISTANCE;
+        graph.add_edge_with_weight(i, j, edge_norm).unwrap();
 
-                graph.add_edge_with_weight(i, j, edge_norm).unwrap();
-
-                // Graph is directed and that cannot be changed.
-                graph.add_edge_with_weight(j, i, edge_norm).unwrap();
-            }
-        }
+        // Graph is directed and that cannot be changed.
+        graph.add_edge_with_weight(j, i, edge_norm).unwrap();
     }
 
     println!("connecting vertices took {:?}", started.elapsed());
@@ -47,19 +40,16 @@ fn main() {
 
     let started = Instant::now();
 
-    let (start, target) = graph.vertices().fold(
-        (VertexId::random(), VertexId::random()),
-        |(start, target), v| {
-            let city = graph.fetch(v).unwrap();
-            if city.name == rusty_graphs::DIJKSTRA_START {
-                (*v, target)
-            } else if city.name == rusty_graphs::DIJKSTRA_TARGET {
-                (start, *v)
-            } else {
-                (start, target)
-            }
-        },
-    );
+    let (start, target) = graph.vertices().fold((None, None), |(s, t), v| {
+        let name = &graph.fetch(v).unwrap().name;
+        (
+            s.or((*name == rusty_graphs::DIJKSTRA_START).then_some(*v)),
+            t.or((*name == rusty_graphs::DIJKSTRA_TARGET).then_some(*v)),
+        )
+    });
+
+    let start = start.expect("start city not found");
+    let target = target.expect("target city not found");
 
     println!("finding start and target took {:?}", started.elapsed());
 
@@ -3,7 +3,7 @@ use std::time::Instant;
 use graphlib::Graph;
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
     let mut vertices = Vec::with_capacity(packages.len());
 
     let mut graph = Graph::new();
@@ -41,7 +41,7 @@ fn main() {
 
     let sorted = graph
         .topo()
-        .map(|v| graph.fetch(v).unwrap().name.as_str())
+        .map(|v| graph.fetch(v).unwrap().id.as_str())
         .collect::<Vec<_>>();
 
     println!("topological sort took {:?}", started.elapsed());
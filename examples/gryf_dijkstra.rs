@@ -8,15 +8,16 @@ use gryf::{
     },
     Graph,
 };
+use rusty_graphs::CityGraph;
 
 fn main() {
-    let cities = rusty_graphs::load_cities();
+    let input: CityGraph = CityGraph::new(rusty_graphs::load_cities().unwrap());
 
     let mut graph = Graph::new_undirected();
 
     let started = Instant::now();
 
-    for city in cities.iter() {
+    for city in input.cities.iter() {
         graph.add_vertex(city);
     }
 
@@ -25,25 +26,25 @@ fn main() {
 
     let started = Instant::now();
 
-    graph.connect_vertices(|src, dst| src.are_connected(dst));
+    for &(i, j, edge) in input.edges.iter() {
+        graph.add_edge(VertexId::from_usize(i), VertexId::from_usize(j), edge);
+    }
 
     println!("connecting vertices took {:?}", started.elapsed());
     println!("edges = {}", graph.edge_count());
 
     let started = Instant::now();
 
-    let (start, target) = graph.vertices().fold(
-        (VertexId::sentinel(), VertexId::sentinel()),
-        |(start, target), v| {
-            if v.attr().name == rusty_graphs::DIJKSTRA_START {
-                (*v.id(), target)
-            } else if v.attr().name == rusty_graphs::DIJKSTRA_TARGET {
-                (start, *v.id())
-            } else {
-                (start, target)
-            }
-        },
-    );
+    let (start, target) = graph.vertices().fold((None, None), |(s, t), v| {
+        let name = &v.attr().name;
+        (
+            s.or((*name == rusty_graphs::DIJKSTRA_START).then_some(*v.id())),
+            t.or((*name == rusty_graphs::DIJKSTRA_TARGET).then_some(*v.id())),
+        )
+    });
+
+    let start = start.expect("start city not found");
+    let target = target.expect("target city not found");
 
     println!("finding start and target took {:?}", started.elapsed());
 
@@ -3,7 +3,7 @@ use std::time::Instant;
 use gryf::{algo::TopoSort, Graph};
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
 
     let mut graph = Graph::new_directed();
 
@@ -27,7 +27,7 @@ fn main() {
 
     let sorted = TopoSort::on(&graph)
         .run()
-        .map(|r| r.map(|v| graph[v].name.as_str()))
+        .map(|r| r.map(|v| graph[v].id.as_str()))
         .collect::<Result<Vec<_>, _>>()
         .unwrap();
 
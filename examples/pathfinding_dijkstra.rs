@@ -2,40 +2,34 @@ use std::{collections::HashMap, time::Instant};
 
 use ordered_float::OrderedFloat;
 use pathfinding::directed::dijkstra::{dijkstra, dijkstra_all};
-use rusty_graphs::City;
+use rusty_graphs::CityGraph;
 
 struct Vertex {
-    id: u64,
     neighbors: Vec<(u64, OrderedFloat<f32>)>,
 }
 
-impl Vertex {
-    fn new(city: &City, cities: &[City]) -> Self {
-        let neighbors = cities
-            .iter()
-            .filter_map(|other| {
-                city.are_connected(other)
-                    .map(|dist| (other.id, OrderedFloat(dist)))
-            })
-            .collect();
-
-        Self {
-            id: city.id,
-            neighbors,
-        }
-    }
-}
-
 fn main() {
-    let cities = rusty_graphs::load_cities();
+    let input: CityGraph = CityGraph::new(rusty_graphs::load_cities().unwrap());
 
-    let mut vertices = HashMap::with_capacity(cities.len());
+    let mut vertices = HashMap::with_capacity(input.cities.len());
 
     let started = Instant::now();
 
-    for city in cities.iter() {
-        let v = Vertex::new(city, &cities);
-        vertices.insert(v.id, v);
+    for city in input.cities.iter() {
+        let v = Vertex {
+            neighbors: Vec::new(),
+        };
+        vertices.insert(city.id, v);
+    }
+
+    for &(i, j, dist) in input.edges.iter() {
+        let (src, dst) = (&input.cities[i], &input.cities[j]);
+
+        let v = vertices.get_mut(&src.id).unwrap();
+        v.neighbors.push((dst.id, OrderedFloat(dist)));
+
+        let v = vertices.get_mut(&dst.id).unwrap();
+        v.neighbors.push((src.id, OrderedFloat(dist)));
     }
 
     println!("adding vertices + edges took {:?}", started.elapsed());
@@ -43,16 +37,16 @@ fn main() {
 
     let started = Instant::now();
 
-    let (start, target) = cities.iter().fold((0, 0), |(start, target), city| {
-        if city.name == rusty_graphs::DIJKSTRA_START {
-            (city.id, target)
-        } else if city.name == rusty_graphs::DIJKSTRA_TARGET {
-            (start, city.id)
-        } else {
-            (start, target)
-        }
+    let (start, target) = input.cities.iter().fold((None, None), |(s, t), city| {
+        (
+            s.or((city.name == rusty_graphs::DIJKSTRA_START).then_some(city.id)),
+            t.or((city.name == rusty_graphs::DIJKSTRA_TARGET).then_some(city.id)),
+        )
     });
 
+    let start = start.expect("start city not found");
+    let target = target.expect("target city not found");
+
     println!("finding start and target took {:?}", started.elapsed());
 
     let started = Instant::now();
@@ -1,4 +1,4 @@
-#![allow(dead_code, unused_variables)]
+#![allow(dead_code, unused_variables, clippy::useless_vec)]
 
 struct Vertex {
     label: usize,
@@ -3,7 +3,7 @@ use std::{collections::HashMap, time::Instant};
 use pathfinding::directed::topological_sort::topological_sort;
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
     let mut inverse_deps = HashMap::with_capacity(packages.len());
     let mut roots = Vec::new();
 
@@ -13,14 +13,14 @@ fn main() {
         let deps = packages
             .iter()
             .filter(|other| other.has_dependency(package))
-            .map(|other| other.name.as_str())
+            .map(|other| other.id.as_str())
             .collect::<Vec<_>>();
 
         if package.deps.is_empty() {
-            roots.push(package.name.as_str());
+            roots.push(package.id.as_str());
         }
 
-        inverse_deps.insert(package.name.as_str(), deps);
+        inverse_deps.insert(package.id.as_str(), deps);
     }
 
     println!("adding vertices + edges took {:?}", started.elapsed());
@@ -28,7 +28,7 @@ fn main() {
 
     let started = Instant::now();
 
-    let sorted = topological_sort(&roots, |name| inverse_deps[name].iter().copied()).unwrap();
+    let sorted = topological_sort(&roots, |id| inverse_deps[id].iter().copied()).unwrap();
 
     println!("topological sort took {:?}", started.elapsed());
     println!("{sorted:?}");
@@ -5,15 +5,16 @@ use petgraph::{
     graph::{Graph, NodeIndex},
     visit::{IntoNodeReferences, NodeRef},
 };
+use rusty_graphs::CityGraph;
 
 fn main() {
-    let cities = rusty_graphs::load_cities();
+    let input: CityGraph = CityGraph::new(rusty_graphs::load_cities().unwrap());
 
     let mut graph = Graph::new_undirected();
 
     let started = Instant::now();
 
-    for city in cities.iter() {
+    for city in input.cities.iter() {
         graph.add_node(city);
     }
 
@@ -22,18 +23,8 @@ fn main() {
 
     let started = Instant::now();
 
-    for i in 0..graph.node_count() {
-        for j in (i + 1)..graph.node_count() {
-            let i = NodeIndex::from(i as u32);
-            let j = NodeIndex::from(j as u32);
-
-            let src = &graph[i];
-            let dst = &graph[j];
-
-            if let Some(edge) = src.are_connected(dst) {
-                graph.add_edge(i, j, edge);
-            }
-        }
+    for &(i, j, edge) in input.edges.iter() {
+        graph.add_edge(NodeIndex::new(i), NodeIndex::new(j), edge);
     }
 
     println!("connecting vertices took {:?}", started.elapsed());
@@ -41,18 +32,16 @@ fn main() {
 
     let started = Instant::now();
 
-    let (start, target) = graph.node_references().fold(
-        (NodeIndex::default(), NodeIndex::default()),
-        |(start, target), v| {
-            if v.weight().name == rusty_graphs::DIJKSTRA_START {
-                (v.id(), target)
-            } else if v.weight().name == rusty_graphs::DIJKSTRA_TARGET {
-                (start, v.id())
-            } else {
-                (start, target)
-            }
-        },
-    );
+    let (start, target) = graph.node_references().fold((None, None), |(s, t), v| {
+        let name = &v.weight().name;
+        (
+            s.or((*name == rusty_graphs::DIJKSTRA_START).then_some(v.id())),
+            t.or((*name == rusty_graphs::DIJKSTRA_TARGET).then_some(v.id())),
+        )
+    });
+
+    let start = start.expect("start city not found");
+    let target = target.expect("target city not found");
 
     println!("finding start and target took {:?}", started.elapsed());
 
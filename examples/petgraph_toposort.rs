@@ -6,7 +6,7 @@ use petgraph::{
 };
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
 
     let mut graph = Graph::new();
 
@@ -43,7 +43,7 @@ fn main() {
     let sorted = toposort(&graph, None)
         .unwrap()
         .into_iter()
-        .map(|v| graph[v].name.as_str())
+        .map(|v| graph[v].id.as_str())
         .collect::<Vec<_>>();
 
     println!("topological sort took {:?}", started.elapsed());
@@ -1,12 +1,12 @@
 use std::time::Instant;
 
-use rusty_graphs::UF32;
+use rusty_graphs::{CityGraph, UF32};
 
 use prepona::prelude::*;
 use prepona::{algo::Dijkstra, graph::SimpleGraph, storage::List};
 
 fn main() {
-    let cities = rusty_graphs::load_cities();
+    let input: CityGraph = CityGraph::new(rusty_graphs::load_cities().unwrap());
 
     // Using a custom wrapper over f32, because `ordered_float::OrderedFloat`
     // does not implement `num_traits::Unsigned`.
@@ -14,7 +14,7 @@ fn main() {
 
     let started = Instant::now();
 
-    for _ in cities.iter() {
+    for _ in input.cities.iter() {
         graph.add_vertex();
     }
 
@@ -23,19 +23,10 @@ fn main() {
 
     let started = Instant::now();
 
-    for i in 0..graph.vertex_count() {
-        let src = &cities[i];
-
-        #[allow(clippy::needless_range_loop)]
-        for j in (i + 1)..graph.vertex_count() {
-            let dst = &cities[j];
-
-            if let Some(edge) = src.are_connected(dst) {
-                graph
-                    .add_edge(i, j, UF32::new(edge).unwrap().into())
-                    .unwrap();
-            }
-        }
+    for &(i, j, edge) in input.edges.iter() {
+        graph
+            .add_edge(i, j, UF32::new(edge).unwrap().into())
+            .unwrap();
     }
 
     println!("connecting vertices took {:?}", started.elapsed());
@@ -46,17 +37,17 @@ fn main() {
     let (start, target) = graph
         .vertices()
         .into_iter()
-        .fold((0, 0), |(start, target), v| {
-            let city = &cities[v];
-            if city.name == rusty_graphs::DIJKSTRA_START {
-                (v, target)
-            } else if city.name == rusty_graphs::DIJKSTRA_TARGET {
-                (start, v)
-            } else {
-                (start, target)
-            }
+        .fold((None, None), |(s, t), v| {
+            let name = &input.cities[v].name;
+            (
+                s.or((*name == rusty_graphs::DIJKSTRA_START).then_some(v)),
+                t.or((*name == rusty_graphs::DIJKSTRA_TARGET).then_some(v)),
+            )
         });
 
+    let start = start.expect("start city not found");
+    let target = target.expect("target city not found");
+
     println!("finding start and target took {:?}", started.elapsed());
 
     let started = Instant::now();
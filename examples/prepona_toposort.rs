@@ -4,7 +4,7 @@ use prepona::prelude::*;
 use prepona::{algo::TopologicalSort, graph::SimpleGraph, storage::DiList};
 
 fn main() {
-    let packages = rusty_graphs::load_tree();
+    let packages = rusty_graphs::load_tree().unwrap();
 
     let mut graph = SimpleGraph::init(DiList::<()>::init());
 
@@ -40,7 +40,7 @@ fn main() {
     let sorted = TopologicalSort::init()
         .execute(&graph)
         .into_iter()
-        .map(|v| packages[v].name.as_str())
+        .map(|v| packages[v].id.as_str())
         .collect::<Vec<_>>();
 
     println!("topological sort took {:?}", started.elapsed());
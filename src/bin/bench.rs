@@ -0,0 +1,310 @@
+use std::time::Duration;
+
+use rusty_graphs::{
+    cli::{self, Options},
+    exercise::{self, Exercise, Phase, Run},
+    libraries::{self, LibraryVisitor},
+    results::{self, Record},
+    stats::Stats,
+    CityGraph, GraphLibrary, Meters, Millimeters, Package, Weight,
+};
+
+struct Bench {
+    warmup: usize,
+    runs: usize,
+    trim: f64,
+    exercises: Vec<Exercise>,
+    weights: Vec<Weight>,
+    libraries: Vec<String>,
+    cities: CityGraph,
+    // Indices of the start and target cities.
+    endpoints: (usize, usize),
+    // Random start and target pairs for the bidirectional search.
+    pairs: Vec<(usize, usize)>,
+    packages: Vec<Package>,
+    // The packages with an injected dependency cycle.
+    cyclic: Vec<Package>,
+    records: Vec<Record>,
+}
+
+impl Bench {
+    fn measure(&mut self, run: impl Fn(&Self) -> Run) -> Vec<(Phase, Vec<Duration>)> {
+        let mut samples: Vec<(Phase, Vec<Duration>)> = Vec::new();
+
+        for i in 0..(self.warmup + self.runs) {
+            let run = run(self);
+
+            if i < self.warmup {
+                continue;
+            }
+
+            self.records.extend(run.records(i - self.warmup));
+
+            for phase in run.phases {
+                let index = match samples.iter().position(|(p, _)| *p == phase.phase) {
+                    Some(index) => index,
+                    None => {
+                        samples.push((phase.phase, Vec::new()));
+                        samples.len() - 1
+                    }
+                };
+
+                samples[index].1.extend(phase.duration);
+            }
+        }
+
+        samples
+    }
+
+    fn report<L: GraphLibrary>(
+        &self,
+        exercise: Exercise,
+        weight: Option<Weight>,
+        samples: Vec<(Phase, Vec<Duration>)>,
+    ) {
+        match weight {
+            Some(weight) => println!("# {} / {} ({})", L::NAME, exercise.label(), weight.name()),
+            None => println!("# {} / {}", L::NAME, exercise.label()),
+        }
+
+        for (phase, samples) in samples {
+            match Stats::new(samples, self.trim) {
+                Some(stats) => println!(
+                    "{}: min {:?}, median {:?}, mean {:?}, std dev {:?}, 95% CI [{:?}, {:?}] (n = {})",
+                    phase.label(),
+                    stats.min,
+                    stats.median,
+                    stats.mean,
+                    stats.std_dev,
+                    stats.ci.0,
+                    stats.ci.1,
+                    stats.samples,
+                ),
+                None => println!("{} {}", phase.label(), phase.missing()),
+            }
+        }
+
+        println!();
+    }
+}
+
+impl LibraryVisitor for Bench {
+    fn visit<L: GraphLibrary>(&mut self) {
+        if !self.libraries.is_empty() && !self.libraries.iter().any(|name| name == L::NAME) {
+            return;
+        }
+
+        for exercise in self.exercises.clone() {
+            // Samples for each type of the edge weights, if the graph is
+            // weighted.
+            let samples = match exercise {
+                Exercise::ShortestPaths
+                | Exercise::Astar
+                | Exercise::Bidirectional
+                | Exercise::SpanningForest => self
+                    .weights
+                    .clone()
+                    .into_iter()
+                    .map(|weight| {
+                        let samples = match weight {
+                            Weight::F32 => self.measure(|bench| {
+                                exercise::on_cities::<L, f32>(
+                                    exercise,
+                                    &bench.cities,
+                                    bench.endpoints,
+                                    &bench.pairs,
+                                )
+                            }),
+                            Weight::F64 => {
+                                let cities = self.cities.with_distance();
+                                self.measure(|bench| {
+                                    exercise::on_cities::<L, f64>(
+                                        exercise,
+                                        &cities,
+                                        bench.endpoints,
+                                        &bench.pairs,
+                                    )
+                                })
+                            }
+                            Weight::Meters => {
+                                let cities = self.cities.with_distance();
+                                self.measure(|bench| {
+                                    exercise::on_cities::<L, Meters>(
+                                        exercise,
+                                        &cities,
+                                        bench.endpoints,
+                                        &bench.pairs,
+                                    )
+                                })
+                            }
+                            Weight::Millimeters => {
+                                let cities = self.cities.with_distance();
+                                self.measure(|bench| {
+                                    exercise::on_cities::<L, Millimeters>(
+                                        exercise,
+                                        &cities,
+                                        bench.endpoints,
+                                        &bench.pairs,
+                                    )
+                                })
+                            }
+                        };
+                        (Some(weight), samples)
+                    })
+                    .collect(),
+                Exercise::ConnectedComponents => vec![(
+                    None,
+                    self.measure(|bench| exercise::connected_components::<L>(&bench.cities)),
+                )],
+                Exercise::TopologicalOrder => vec![(
+                    None,
+                    self.measure(|bench| exercise::topological_order::<L>(&bench.packages)),
+                )],
+                Exercise::TopologicalLevels => vec![(
+                    None,
+                    self.measure(|bench| exercise::topological_levels::<L>(&bench.packages)),
+                )],
+                Exercise::DependencyCycle => vec![(
+                    None,
+                    self.measure(|bench| exercise::dependency_cycle::<L>(&bench.cyclic)),
+                )],
+            };
+
+            for (weight, samples) in samples {
+                self.report::<L>(exercise, weight, samples);
+            }
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: bench [--warmup N] [--runs N] [--trim FRACTION] [--exercise NAME]... {} [--json PATH] [--csv PATH] [LIBRARY]...",
+        Options::USAGE
+    );
+    std::process::exit(2);
+}
+
+// Reports the error of loading the input or writing the results and exits.
+fn fail(error: String) -> ! {
+    eprintln!("{error}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut options = Options::default();
+    let mut warmup = 1;
+    let mut runs = 10;
+    let mut trim = 0.1;
+    let mut exercises = Vec::new();
+    let mut json = None;
+    let mut csv = None;
+    let mut libraries = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if options.parse(&arg, &mut args, usage) {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--warmup" => warmup = cli::value(&mut args, usage),
+            "--runs" => runs = cli::value(&mut args, usage),
+            "--trim" => trim = cli::value(&mut args, usage),
+            "--exercise" => {
+                let name = cli::value::<String>(&mut args, usage);
+                let exercise = Exercise::ALL
+                    .into_iter()
+                    .find(|e| e.name() == name)
+                    .unwrap_or_else(|| usage());
+                exercises.push(exercise);
+            }
+            "--json" => json = Some(cli::value::<String>(&mut args, usage)),
+            "--csv" => csv = Some(cli::value::<String>(&mut args, usage)),
+            "--help" | "-h" => usage(),
+            _ => libraries.push(arg),
+        }
+    }
+
+    if exercises.is_empty() {
+        exercises = Exercise::ALL.to_vec();
+    }
+
+    let weights = if options.weights.is_empty() {
+        Weight::DEFAULT.to_vec()
+    } else {
+        options.weights.clone()
+    };
+
+    let cities = if exercises.contains(&Exercise::ShortestPaths)
+        || exercises.contains(&Exercise::Astar)
+        || exercises.contains(&Exercise::Bidirectional)
+        || exercises.contains(&Exercise::SpanningForest)
+        || exercises.contains(&Exercise::ConnectedComponents)
+    {
+        CityGraph::new(options.cities().unwrap_or_else(|error| fail(error)))
+    } else {
+        CityGraph::new(Vec::new())
+    };
+
+    // Only these exercises need the start and target cities, so that the
+    // others run on datasets without them.
+    let endpoints =
+        if exercises.contains(&Exercise::ShortestPaths) || exercises.contains(&Exercise::Astar) {
+            options
+                .endpoints(&cities)
+                .unwrap_or_else(|error| fail(error))
+        } else {
+            (0, 0)
+        };
+
+    let pairs = options.random_pairs(cities.cities.len());
+
+    let packages = if exercises.contains(&Exercise::TopologicalOrder)
+        || exercises.contains(&Exercise::TopologicalLevels)
+        || exercises.contains(&Exercise::DependencyCycle)
+    {
+        options.tree().unwrap_or_else(|error| fail(error))
+    } else {
+        Vec::new()
+    };
+
+    let cyclic = if exercises.contains(&Exercise::DependencyCycle) {
+        let cyclic = cli::inject_cycle(&packages).map(|(cyclic, _)| cyclic);
+        if cyclic.is_none() {
+            exercises.retain(|&e| e != Exercise::DependencyCycle);
+        }
+        cyclic.unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let mut bench = Bench {
+        warmup,
+        runs,
+        trim,
+        exercises,
+        weights,
+        libraries,
+        cities,
+        endpoints,
+        pairs,
+        packages,
+        cyclic,
+        records: Vec::new(),
+    };
+
+    libraries::for_each(&mut bench);
+
+    if let Some(path) = json {
+        if let Err(error) = results::write_json(&path, &bench.records) {
+            fail(format!("{path}: {error}"));
+        }
+    }
+
+    if let Some(path) = csv {
+        if let Err(error) = results::write_csv(&path, &bench.records) {
+            fail(format!("{path}: {error}"));
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use rusty_graphs::{
+    cli::{self, Options},
+    exercise::{self, Exercise},
+    libraries::{self, LibraryVisitor},
+    results::{self, Record},
+    CityGraph, GraphLibrary, Meters, Millimeters, Package, Weight,
+};
+
+struct Compare {
+    filter: Vec<String>,
+    weights: Vec<Weight>,
+    cities: CityGraph,
+    // Indices of the start and target cities.
+    endpoints: (usize, usize),
+    // Random start and target pairs for the bidirectional search.
+    pairs: Vec<(usize, usize)>,
+    packages: Vec<Package>,
+    // The packages with an injected dependency cycle, if the tree has a chain
+    // to close.
+    cyclic: Option<Vec<Package>>,
+    records: Vec<Record>,
+}
+
+impl LibraryVisitor for Compare {
+    fn visit<L: GraphLibrary>(&mut self) {
+        if !self.filter.is_empty() && !self.filter.iter().any(|name| name == L::NAME) {
+            return;
+        }
+
+        let mut runs = Vec::new();
+
+        let (endpoints, pairs) = (self.endpoints, &self.pairs);
+
+        for exercise in [
+            Exercise::ShortestPaths,
+            Exercise::Astar,
+            Exercise::Bidirectional,
+            Exercise::SpanningForest,
+        ] {
+            for weight in self.weights.iter() {
+                runs.push(match weight {
+                    Weight::F32 => {
+                        exercise::on_cities::<L, f32>(exercise, &self.cities, endpoints, pairs)
+                    }
+                    Weight::F64 => exercise::on_cities::<L, f64>(
+                        exercise,
+                        &self.cities.with_distance(),
+                        endpoints,
+                        pairs,
+                    ),
+                    Weight::Meters => exercise::on_cities::<L, Meters>(
+                        exercise,
+                        &self.cities.with_distance(),
+                        endpoints,
+                        pairs,
+                    ),
+                    Weight::Millimeters => exercise::on_cities::<L, Millimeters>(
+                        exercise,
+                        &self.cities.with_distance(),
+                        endpoints,
+                        pairs,
+                    ),
+                });
+            }
+        }
+
+        runs.extend([
+            exercise::connected_components::<L>(&self.cities),
+            exercise::topological_order::<L>(&self.packages),
+            exercise::topological_levels::<L>(&self.packages),
+        ]);
+
+        if let Some(cyclic) = &self.cyclic {
+            runs.push(exercise::dependency_cycle::<L>(cyclic));
+        }
+
+        for run in runs {
+            match run.weight {
+                Some(weight) => println!(
+                    "# {} / {} ({})",
+                    run.library,
+                    run.exercise.label(),
+                    weight.name()
+                ),
+                None => println!("# {} / {}", run.library, run.exercise.label()),
+            }
+            print!("{run}");
+            println!();
+
+            self.records.extend(run.records(0));
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: compare {} [--json PATH] [--csv PATH] [LIBRARY]...",
+        Options::USAGE
+    );
+    std::process::exit(2);
+}
+
+// Reports the error of loading the input or writing the results and exits.
+fn fail(error: String) -> ! {
+    eprintln!("{error}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut options = Options::default();
+    let mut json = None;
+    let mut csv = None;
+    let mut filter = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if options.parse(&arg, &mut args, usage) {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--json" => json = Some(cli::value::<String>(&mut args, usage)),
+            "--csv" => csv = Some(cli::value::<String>(&mut args, usage)),
+            "--help" | "-h" => usage(),
+            _ => filter.push(arg),
+        }
+    }
+
+    let weights = if options.weights.is_empty() {
+        Weight::DEFAULT.to_vec()
+    } else {
+        options.weights.clone()
+    };
+
+    let packages = options.tree().unwrap_or_else(|error| fail(error));
+    let cyclic = cli::inject_cycle(&packages).map(|(cyclic, _)| cyclic);
+
+    let cities = CityGraph::new(options.cities().unwrap_or_else(|error| fail(error)));
+    let endpoints = options
+        .endpoints(&cities)
+        .unwrap_or_else(|error| fail(error));
+    let pairs = options.random_pairs(cities.cities.len());
+
+    let mut compare = Compare {
+        filter,
+        weights,
+        cities,
+        endpoints,
+        pairs,
+        packages,
+        cyclic,
+        records: Vec::new(),
+    };
+
+    libraries::for_each(&mut compare);
+
+    if let Some(path) = json {
+        if let Err(error) = results::write_json(&path, &compare.records) {
+            fail(format!("{path}: {error}"));
+        }
+    }
+
+    if let Some(path) = csv {
+        if let Err(error) = results::write_csv(&path, &compare.records) {
+            fail(format!("{path}: {error}"));
+        }
+    }
+}
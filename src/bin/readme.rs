@@ -0,0 +1,450 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use rusty_graphs::{
+    cli,
+    exercise::{self, Exercise, Phase, Run},
+    libraries::{self, LibraryVisitor},
+    CityGraph, GraphLibrary, Package,
+};
+
+const SUMMARY_START: &str = "<!-- summary start -->";
+const SUMMARY_END: &str = "<!-- summary end -->";
+
+struct Library {
+    name: &'static str,
+    algorithms: &'static [Phase],
+    adds_vertices: bool,
+    runs: Vec<Run>,
+}
+
+struct Readme {
+    // Directory of the crate, which the paths in the document are relative to.
+    root: PathBuf,
+    // Whether the examples and the exercises are run, otherwise only the code
+    // is updated.
+    run: bool,
+    cities: Option<CityGraph>,
+    // Indices of the start and target cities of the shortest paths.
+    endpoints: (usize, usize),
+    packages: Option<Vec<Package>>,
+    cyclic: Option<Vec<Package>>,
+    libraries: Vec<Library>,
+}
+
+impl LibraryVisitor for Readme {
+    fn visit<L: GraphLibrary>(&mut self) {
+        let mut runs = Vec::new();
+
+        if let Some(cities) = &self.cities {
+            let (start, target) = self.endpoints;
+            runs.push(exercise::shortest_paths::<L, f32>(cities, start, target));
+            runs.push(exercise::astar::<L, f32>(cities, start, target));
+
+            let pairs =
+                rusty_graphs::synthetic::pairs(cities.cities.len(), rusty_graphs::RANDOM_PAIRS, 0);
+            runs.push(exercise::bidirectional::<L, f32>(cities, &pairs));
+            runs.push(exercise::spanning_forest::<L, f32>(cities));
+            runs.push(exercise::connected_components::<L>(cities));
+        }
+
+        if let Some(packages) = &self.packages {
+            runs.push(exercise::topological_order::<L>(packages));
+            runs.push(exercise::topological_levels::<L>(packages));
+        }
+
+        if let Some(cyclic) = &self.cyclic {
+            runs.push(exercise::dependency_cycle::<L>(cyclic));
+        }
+
+        self.libraries.push(Library {
+            name: L::NAME,
+            algorithms: L::ALGORITHMS,
+            adds_vertices: L::ADDS_VERTICES,
+            runs,
+        });
+    }
+}
+
+impl Readme {
+    // Output of the example if it belongs to an exercise and the examples are
+    // run.
+    fn output(&self, example: &str) -> Option<String> {
+        let exercise = Exercise::ALL
+            .into_iter()
+            .filter_map(Exercise::example)
+            .any(|suffix| example.ends_with(&format!("_{suffix}")));
+
+        (self.run && exercise).then(|| run_example(&self.root, example))
+    }
+
+    // Output of the run of the library adapter in the given file for the
+    // exercise showcased by the method, if the exercises are run.
+    fn adapter_output(&self, path: &str, method: &str) -> Option<String> {
+        let name = Path::new(path).file_stem()?.to_str()?;
+        let exercise = Exercise::ALL
+            .into_iter()
+            .find(|exercise| exercise.method() == Some(method))?;
+
+        let library = self.libraries.iter().find(|library| library.name == name)?;
+        let run = library.runs.iter().find(|run| run.exercise == exercise)?;
+
+        Some(run.to_string())
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = String::new();
+
+        for exercise in Exercise::ALL {
+            let phases = exercise.phases();
+
+            writeln!(summary, "#### Summary of {}", exercise.label()).unwrap();
+            writeln!(summary).unwrap();
+
+            write!(summary, "| library |").unwrap();
+            for phase in phases {
+                write!(summary, " {} |", phase.label()).unwrap();
+            }
+            writeln!(summary).unwrap();
+
+            write!(summary, "| --- |").unwrap();
+            for _ in phases {
+                write!(summary, " --- |").unwrap();
+            }
+            writeln!(summary).unwrap();
+
+            for library in self.libraries.iter() {
+                let run = library.runs.iter().find(|run| run.exercise == exercise);
+
+                write!(summary, "| `{}` |", library.name).unwrap();
+                for phase in phases {
+                    let supported = match phase {
+                        Phase::AddVertices => library.adds_vertices,
+                        phase if phase.is_algorithm() => library.algorithms.contains(phase),
+                        _ => true,
+                    };
+
+                    let duration = run
+                        .and_then(|run| run.phases.iter().find(|p| p.phase == *phase))
+                        .and_then(|p| p.duration);
+
+                    match (supported, duration) {
+                        (false, _) => write!(summary, " {} |", phase.missing()),
+                        (true, Some(duration)) => write!(summary, " {duration:.2?} |"),
+                        (true, None) => write!(summary, " ✓ |"),
+                    }
+                    .unwrap();
+                }
+                writeln!(summary).unwrap();
+            }
+
+            writeln!(summary).unwrap();
+        }
+
+        summary
+    }
+
+    fn generate(&self, readme: &str) -> String {
+        let mut out = Vec::new();
+        let mut lines = readme.lines().peekable();
+
+        while let Some(line) = lines.next() {
+            out.push(line.to_string());
+
+            if line == SUMMARY_START && self.run {
+                for line in lines.by_ref() {
+                    if line == SUMMARY_END {
+                        break;
+                    }
+                }
+
+                out.push(String::new());
+                out.extend(self.summary().lines().map(String::from));
+                out.push(SUMMARY_END.to_string());
+                continue;
+            }
+
+            let Some(link) = line
+                .strip_prefix("[source](")
+                .and_then(|line| line.strip_suffix(')'))
+            else {
+                continue;
+            };
+
+            // Links with a method, such as `src/libraries/petgraph.rs#astar`,
+            // show the method of the library adapter instead of an example.
+            let (path, method) = match link.split_once('#') {
+                Some((path, method)) => (path, Some(method)),
+                None => (link, None),
+            };
+
+            // Copy everything up to the code block.
+            for line in lines.by_ref() {
+                out.push(line.to_string());
+                if line == "```rust" {
+                    break;
+                }
+            }
+
+            // Replace the code block with the current example.
+            for line in lines.by_ref() {
+                if line == "```" {
+                    break;
+                }
+            }
+
+            let source = read(&self.root.join(path));
+            let code = match method {
+                Some(method) => method_snippet(&source, method).unwrap_or_else(|| {
+                    eprintln!("{path}: no method {method}");
+                    std::process::exit(1);
+                }),
+                None => snippet(&source),
+            };
+            out.extend(code.lines().map(String::from));
+            out.push("```".to_string());
+
+            // Replace the block with the output that directly follows the code.
+            let output = match method {
+                Some(method) => self.adapter_output(path, method),
+                None => {
+                    let example = path.trim_start_matches("examples/").trim_end_matches(".rs");
+                    self.output(example)
+                }
+            };
+
+            let Some(output) = output else {
+                continue;
+            };
+
+            while lines.peek() == Some(&"") {
+                out.push(lines.next().unwrap().to_string());
+            }
+
+            if lines.peek() == Some(&"```") {
+                lines.next();
+                for line in lines.by_ref() {
+                    if line == "```" {
+                        break;
+                    }
+                }
+
+                out.push("```".to_string());
+                out.extend(output.lines().map(String::from));
+                out.push("```".to_string());
+            } else {
+                // Newly added example without output yet.
+                out.push("```".to_string());
+                out.extend(output.lines().map(String::from));
+                out.push("```".to_string());
+                out.push(String::new());
+            }
+        }
+
+        out.push(String::new());
+        out.join("\n")
+    }
+}
+
+// Converts an example source into the form presented in the document, that is,
+// without the timing code and `main` function wrapper.
+fn snippet(source: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_main = false;
+
+    for line in source.lines() {
+        if line.starts_with("#![") {
+            continue;
+        }
+
+        if line == "fn main() {" {
+            in_main = true;
+            continue;
+        }
+
+        if in_main && line == "}" {
+            in_main = false;
+            continue;
+        }
+
+        let line = if in_main {
+            line.strip_prefix("    ").unwrap_or(line)
+        } else {
+            line
+        };
+
+        let trimmed = line.trim_start();
+
+        // Algorithm results are printed right after the algorithm is run.
+        let algorithm_took = [
+            Phase::Dijkstra,
+            Phase::DijkstraWithGoal,
+            Phase::ShortestPath,
+            Phase::Astar,
+            Phase::SpanningForest,
+            Phase::ConnectedComponents,
+            Phase::TopologicalSort,
+            Phase::TopologicalLevels,
+            Phase::FindCycle,
+        ]
+        .into_iter()
+        .any(|phase| trimmed.contains(&format!("\"{} took {{:?}}\"", phase.label())));
+
+        if algorithm_took && lines.last().is_some_and(|last| last.is_empty()) {
+            lines.pop();
+        }
+
+        if trimmed.starts_with("use rusty_graphs")
+            || trimmed == "use std::time::Instant;"
+            || trimmed.starts_with("let started = Instant::now();")
+            || (trimmed.starts_with("println!(") && trimmed.contains(" took {:?}"))
+        {
+            continue;
+        }
+
+        let mut line = line
+            .replace("rusty_graphs::DIJKSTRA_START", "START")
+            .replace("rusty_graphs::DIJKSTRA_TARGET", "TARGET")
+            .replace("rusty_graphs::", "");
+
+        if line.starts_with("use std::{") && line.contains("time::Instant") {
+            line = line
+                .replace(", time::Instant", "")
+                .replace("time::Instant, ", "");
+
+            if !line.contains(',') {
+                line = line.replace(['{', '}'], "");
+            }
+        }
+
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+
+        lines.push(line);
+    }
+
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+// The method of a library adapter as presented in the document, that is,
+// without the indentation of the `impl` block. `None` if there is no such
+// method.
+fn method_snippet(source: &str, method: &str) -> Option<String> {
+    let mut lines = source
+        .lines()
+        .skip_while(|line| {
+            !line.starts_with(&format!("    fn {method}<"))
+                && !line.starts_with(&format!("    fn {method}("))
+        })
+        .peekable();
+
+    lines.peek()?;
+
+    let mut method = Vec::new();
+    for line in lines {
+        method.push(line.strip_prefix("    ").unwrap_or(line));
+        if line == "    }" {
+            break;
+        }
+    }
+
+    Some(method.join("\n"))
+}
+
+fn read(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("{}: {error}", path.display());
+        std::process::exit(1);
+    })
+}
+
+// Runs the example in release mode from the crate directory and returns what it
+// printed.
+fn run_example(root: &Path, example: &str) -> String {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    let output = Command::new(cargo)
+        .args(["run", "--quiet", "--release", "--example", example])
+        .current_dir(root)
+        .stderr(Stdio::inherit())
+        .output()
+        .unwrap_or_else(|error| {
+            eprintln!("cannot run example {example}: {error}");
+            std::process::exit(1);
+        });
+
+    if !output.status.success() {
+        eprintln!("example {example} failed: {}", output.status);
+        std::process::exit(1);
+    }
+
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn usage() -> ! {
+    eprintln!("usage: readme [--no-run] [--output PATH]");
+    std::process::exit(2);
+}
+
+// Reports the error of loading the input and exits.
+fn fail(error: String) -> ! {
+    eprintln!("{error}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut run = true;
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut output = root.join("README.md");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-run" => run = false,
+            "--output" => output = cli::value(&mut args, usage),
+            _ => usage(),
+        }
+    }
+
+    let mut readme = Readme {
+        root,
+        run,
+        cities: None,
+        endpoints: (0, 0),
+        packages: None,
+        cyclic: None,
+        libraries: Vec::new(),
+    };
+
+    if run {
+        let cities = rusty_graphs::load_cities().unwrap_or_else(|error| fail(error.to_string()));
+        let cities = CityGraph::new(cities);
+        readme.endpoints = cli::endpoints(
+            &cities,
+            rusty_graphs::DIJKSTRA_START,
+            rusty_graphs::DIJKSTRA_TARGET,
+        )
+        .unwrap_or_else(|error| fail(error));
+        readme.cities = Some(cities);
+
+        let packages =
+            cli::load_tree(&rusty_graphs::tree_path()).unwrap_or_else(|error| fail(error));
+        readme.cyclic = cli::inject_cycle(&packages).map(|(cyclic, _)| cyclic);
+        readme.packages = Some(packages);
+    }
+
+    libraries::for_each(&mut readme);
+
+    let current = read(&readme.root.join("README.md"));
+
+    if let Err(error) = std::fs::write(&output, readme.generate(&current)) {
+        eprintln!("{}: {error}", output.display());
+        std::process::exit(1);
+    }
+}
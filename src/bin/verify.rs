@@ -0,0 +1,520 @@
+use rusty_graphs::{
+    cli::{self, Options},
+    libraries::{self, LibraryVisitor},
+    reference::{self, PathError},
+    CityGraph, Distance, GraphLibrary, Meters, Millimeters, Package, Weight,
+};
+
+// Maximum number of reported mismatches per library.
+const MAX_REPORTED: usize = 10;
+
+// Shortest paths with edge weights of one type.
+struct VerifyDistances<D> {
+    cities: CityGraph<D>,
+    start: usize,
+    target: usize,
+    distances: Vec<D>,
+    // Vertices visited by the reference A*.
+    visited: usize,
+    // Weight and components of the reference minimum spanning forest.
+    forest: (D, usize),
+    failed: bool,
+}
+
+impl<D: Distance> LibraryVisitor for VerifyDistances<D> {
+    fn visit<L: GraphLibrary>(&mut self) {
+        let failed = {
+            let graph = L::connect_cities(L::add_cities(&self.cities), &self.cities);
+            self.shortest_paths::<L>(&graph)
+                | self.shortest_path::<L>(&graph)
+                | self.astar::<L>(&graph)
+                | self.spanning_forest::<L>(&graph)
+        };
+        self.failed |= failed;
+    }
+}
+
+impl<D: Distance> VerifyDistances<D> {
+    // Each returns whether the library failed.
+    fn shortest_paths<L: GraphLibrary>(&self, graph: &L::Cities<'_, D>) -> bool {
+        let weight = D::WEIGHT.name();
+        let start = L::city_id(graph, self.start);
+
+        let Some(distances) = L::dijkstra_distances(graph, start) else {
+            println!("{}: shortest paths ({weight}) not available", L::NAME);
+            return false;
+        };
+
+        let mismatches = reference::verify_distances(&self.distances, &distances);
+
+        if mismatches.is_empty() {
+            println!("{}: shortest paths ({weight}) ok", L::NAME);
+            return false;
+        }
+
+        // Converting the weights loses precision, so the differences are
+        // expected.
+        let failed = L::WEIGHTS.contains(&D::WEIGHT);
+
+        if failed {
+            println!(
+                "{}: shortest paths ({weight}) differ in {} vertices",
+                L::NAME,
+                mismatches.len()
+            );
+        } else {
+            println!(
+                "{}: shortest paths ({weight}) differ in {} vertices, as the weights are converted",
+                L::NAME,
+                mismatches.len()
+            );
+        }
+
+        for mismatch in mismatches.iter().take(MAX_REPORTED) {
+            println!(
+                "  {} (#{}): expected {}, got {}",
+                self.cities.cities[mismatch.index].name,
+                mismatch.index,
+                mismatch.expected,
+                mismatch.actual
+            );
+        }
+
+        if mismatches.len() > MAX_REPORTED {
+            println!("  ...");
+        }
+
+        failed
+    }
+
+    fn shortest_path<L: GraphLibrary>(&self, graph: &L::Cities<'_, D>) -> bool {
+        let weight = D::WEIGHT.name();
+        let start = L::city_id(graph, self.start);
+        let target = L::city_id(graph, self.target);
+
+        let Some(path) = L::shortest_path(graph, start, target) else {
+            println!("{}: shortest path ({weight}) not available", L::NAME);
+            return false;
+        };
+
+        let expected = self.distances[self.target];
+        let converted = !L::WEIGHTS.contains(&D::WEIGHT);
+
+        match reference::verify_path(&self.cities, self.start, self.target, &path) {
+            Err(PathError::Distance { .. }) if converted => {
+                println!(
+                    "{}: shortest path ({weight}) differs from its edges, as the weights are converted",
+                    L::NAME
+                );
+                false
+            }
+            Err(error) => {
+                println!("{}: shortest path ({weight}) invalid: {error}", L::NAME);
+                true
+            }
+            Ok(()) if !converted && reference::distances_differ(expected, path.distance) => {
+                println!(
+                    "{}: shortest path ({weight}) has distance {}, expected {expected}",
+                    L::NAME,
+                    path.distance
+                );
+                true
+            }
+            Ok(()) => {
+                println!("{}: shortest path ({weight}) ok", L::NAME);
+                false
+            }
+        }
+    }
+
+    fn astar<L: GraphLibrary>(&self, graph: &L::Cities<'_, D>) -> bool {
+        let weight = D::WEIGHT.name();
+        let start = L::city_id(graph, self.start);
+        let target = L::city_id(graph, self.target);
+
+        let Some((distance, visited)) = L::astar(graph, start, target) else {
+            println!("{}: astar ({weight}) not available", L::NAME);
+            return false;
+        };
+
+        let expected = self.distances[self.target];
+
+        // The number of visited vertices depends on how the ties are broken,
+        // so it is only reported.
+        if reference::distances_differ(expected, distance) {
+            println!(
+                "{}: astar ({weight}) has distance {distance}, expected {expected}",
+                L::NAME
+            );
+            true
+        } else {
+            println!(
+                "{}: astar ({weight}) ok, visited {visited} (reference {})",
+                L::NAME,
+                self.visited
+            );
+            false
+        }
+    }
+
+    fn spanning_forest<L: GraphLibrary>(&self, graph: &L::Cities<'_, D>) -> bool {
+        let weight = D::WEIGHT.name();
+
+        let Some((total, components)) = L::spanning_forest(graph) else {
+            println!("{}: spanning forest ({weight}) not available", L::NAME);
+            return false;
+        };
+
+        let (expected, expected_components) = self.forest;
+        let converted = !L::WEIGHTS.contains(&D::WEIGHT);
+
+        if components != expected_components {
+            println!(
+                "{}: spanning forest ({weight}) has {components} components, expected {expected_components}",
+                L::NAME
+            );
+            true
+        } else if !reference::distances_differ(expected, total) {
+            println!("{}: spanning forest ({weight}) ok", L::NAME);
+            false
+        } else if converted {
+            println!(
+                "{}: spanning forest ({weight}) has weight {total}, expected {expected}, as the weights are converted",
+                L::NAME
+            );
+            false
+        } else {
+            println!(
+                "{}: spanning forest ({weight}) has weight {total}, expected {expected}",
+                L::NAME
+            );
+            true
+        }
+    }
+}
+
+// Returns whether any library failed.
+fn verify_distances<D: Distance>(
+    cities: CityGraph<D>,
+    start: usize,
+    target: usize,
+    pairs: &[(usize, usize)],
+) -> bool {
+    let distances = reference::dijkstra(&cities, start);
+    let (distance, visited) = reference::astar(&cities, start, target);
+    let mut failed = false;
+
+    if reference::distances_differ(distances[target], distance) {
+        failed = true;
+        println!(
+            "reference astar ({}): distance {distance}, expected {}",
+            D::WEIGHT.name(),
+            distances[target]
+        );
+    }
+
+    let neighbors = reference::graph_neighbors(&cities);
+    let mismatches = pairs
+        .iter()
+        .filter(|&&(start, target)| {
+            let (distance, _) = reference::bidirectional_dijkstra(&neighbors, start, target);
+            reference::distances_differ(reference::dijkstra_on(&neighbors, start)[target], distance)
+        })
+        .count();
+
+    if mismatches > 0 {
+        failed = true;
+        println!(
+            "reference bidirectional dijkstra ({}): differs in {mismatches} of {} pairs",
+            D::WEIGHT.name(),
+            pairs.len()
+        );
+    } else {
+        println!(
+            "reference bidirectional dijkstra ({}): ok on {} pairs",
+            D::WEIGHT.name(),
+            pairs.len()
+        );
+    }
+
+    let forest = reference::kruskal(&cities);
+
+    let mut verify = VerifyDistances {
+        cities,
+        start,
+        target,
+        distances,
+        visited,
+        forest,
+        failed,
+    };
+
+    libraries::for_each(&mut verify);
+    verify.failed
+}
+
+struct VerifyComponents {
+    cities: CityGraph,
+    labels: Vec<usize>,
+    failed: bool,
+}
+
+impl LibraryVisitor for VerifyComponents {
+    fn visit<L: GraphLibrary>(&mut self) {
+        let graph = L::connect_cities(L::add_cities(&self.cities), &self.cities);
+
+        let Some(labels) = L::connected_components(&graph) else {
+            println!("{}: connected components not available", L::NAME);
+            return;
+        };
+
+        match reference::components_mismatch(&self.labels, &labels) {
+            Some(i) => {
+                self.failed = true;
+                println!(
+                    "{}: connected components differ at {} (#{i})",
+                    L::NAME,
+                    self.cities.cities[i].name
+                );
+            }
+            None => println!("{}: connected components ok", L::NAME),
+        }
+    }
+}
+
+// The reference components are cross-checked with the number of trees of the
+// minimum spanning forest and with the cities reachable from the start.
+// Returns whether any check failed.
+fn verify_components(cities: CityGraph, start: usize) -> bool {
+    let labels = reference::connected_components(&cities);
+    let count = labels.iter().max().map_or(0, |&max| max + 1);
+    let (_, trees) = reference::kruskal(&cities);
+    let mut failed = false;
+
+    if count != trees {
+        failed = true;
+        println!("reference components: {count} components, but {trees} trees of spanning forest");
+    }
+
+    let distances = reference::dijkstra(&cities, start);
+    let mismatch = (0..labels.len())
+        .find(|&i| (labels[i] == labels[start]) != (distances[i] != f32::INFINITY));
+
+    if let Some(i) = mismatch {
+        failed = true;
+        println!(
+            "reference components: {} (#{i}) is {} from the start",
+            cities.cities[i].name,
+            if distances[i] == f32::INFINITY {
+                "unreachable"
+            } else {
+                "reachable"
+            }
+        );
+    }
+
+    if !failed {
+        println!("reference components: ok, count = {count}");
+    }
+
+    let mut verify = VerifyComponents {
+        cities,
+        labels,
+        failed,
+    };
+
+    libraries::for_each(&mut verify);
+    verify.failed
+}
+
+struct Verify {
+    packages: Vec<Package>,
+    // The packages with an injected dependency cycle and the cycle, if the
+    // tree has a chain to close.
+    cyclic: Option<(Vec<Package>, Vec<String>)>,
+    failed: bool,
+}
+
+impl LibraryVisitor for Verify {
+    fn visit<L: GraphLibrary>(&mut self) {
+        self.topological_order::<L>();
+        self.topological_levels::<L>();
+        self.dependency_cycle::<L>();
+    }
+}
+
+impl Verify {
+    fn topological_order<L: GraphLibrary>(&mut self) {
+        let graph = L::connect_packages(L::add_packages(&self.packages));
+
+        let Some(sorted) = L::toposort(&graph) else {
+            println!("{}: topological order not available", L::NAME);
+            return;
+        };
+
+        match reference::verify_topological_order(&self.packages, &sorted) {
+            Ok(()) => println!("{}: topological order ok", L::NAME),
+            Err(error) => {
+                self.failed = true;
+                println!("{}: topological order invalid: {error}", L::NAME);
+            }
+        }
+    }
+
+    fn topological_levels<L: GraphLibrary>(&mut self) {
+        let graph = L::connect_packages(L::add_packages(&self.packages));
+
+        let Some(levels) = L::topological_levels(&graph) else {
+            println!("{}: topological levels not available", L::NAME);
+            return;
+        };
+
+        let expected = reference::topological_levels(&self.packages).unwrap();
+        let width = |levels: &[Vec<&str>]| levels.iter().map(Vec::len).max();
+
+        match reference::verify_topological_levels(&self.packages, &levels) {
+            Ok(()) if levels.len() != expected.len() || width(&levels) != width(&expected) => {
+                self.failed = true;
+                println!(
+                    "{}: topological levels differ from the reference in critical path or width",
+                    L::NAME
+                );
+            }
+            Ok(()) => println!("{}: topological levels ok", L::NAME),
+            Err(error) => {
+                self.failed = true;
+                println!("{}: topological levels invalid: {error}", L::NAME);
+            }
+        }
+    }
+
+    fn dependency_cycle<L: GraphLibrary>(&mut self) {
+        let Some((cyclic, injected)) = &self.cyclic else {
+            return;
+        };
+
+        let graph = L::connect_packages(L::add_packages(cyclic));
+
+        let Some(cycle) = L::find_cycle(&graph) else {
+            println!("{}: dependency cycle not available", L::NAME);
+            return;
+        };
+
+        // Libraries may report any of the cycles, so the cycle is only checked
+        // to go through the injected dependency, like the reference one.
+        match reference::verify_injected_cycle(cyclic, &cycle, injected) {
+            Ok(()) => println!(
+                "{}: dependency cycle ok, length {} (injected {})",
+                L::NAME,
+                cycle.len(),
+                injected.len()
+            ),
+            Err(error) => {
+                self.failed = true;
+                println!("{}: dependency cycle invalid: {error}", L::NAME);
+            }
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: verify {}", Options::USAGE);
+    std::process::exit(2);
+}
+
+// Reports the error of loading the input and exits.
+fn fail(error: String) -> ! {
+    eprintln!("{error}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut options = Options::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if !options.parse(&arg, &mut args, usage) {
+            usage();
+        }
+    }
+
+    let weights = if options.weights.is_empty() {
+        Weight::ALL.to_vec()
+    } else {
+        options.weights.clone()
+    };
+
+    let cities = options.cities().unwrap_or_else(|error| fail(error));
+    let expected = reference::city_pairs(&cities);
+    let cities = CityGraph::<f32>::new(cities);
+    let (start, target) = options
+        .endpoints(&cities)
+        .unwrap_or_else(|error| fail(error));
+    let pairs = options.random_pairs(cities.cities.len());
+    let actual = &cities.edges;
+    let mut failed = &expected != actual;
+
+    if failed {
+        println!(
+            "spatial index: found {} connected pairs, expected {}",
+            actual.len(),
+            expected.len()
+        );
+    } else {
+        println!("spatial index: ok");
+    }
+
+    let packages = options.tree().unwrap_or_else(|error| fail(error));
+    let cyclic = cli::inject_cycle(&packages);
+
+    match (reference::find_cycle(&packages), &cyclic) {
+        (Some(cycle), _) => {
+            failed = true;
+            println!(
+                "reference cycle: found {} in the original tree",
+                cycle.join(" -> ")
+            );
+        }
+        (None, None) => {}
+        (None, Some((cyclic, injected))) => match reference::find_cycle(cyclic) {
+            Some(cycle) => match reference::verify_injected_cycle(cyclic, &cycle, injected) {
+                Ok(()) => println!("reference cycle: {}", cycle.join(" -> ")),
+                Err(error) => {
+                    failed = true;
+                    println!("reference cycle: invalid: {error}");
+                }
+            },
+            None => {
+                failed = true;
+                println!("reference cycle: not found in the injected tree");
+            }
+        },
+    }
+
+    failed |= verify_components(cities.clone(), start);
+
+    for weight in weights {
+        failed |= match weight {
+            Weight::F32 => verify_distances(cities.clone(), start, target, &pairs),
+            Weight::F64 => verify_distances::<f64>(cities.with_distance(), start, target, &pairs),
+            Weight::Meters => {
+                verify_distances::<Meters>(cities.with_distance(), start, target, &pairs)
+            }
+            Weight::Millimeters => {
+                verify_distances::<Millimeters>(cities.with_distance(), start, target, &pairs)
+            }
+        };
+    }
+
+    let mut verify = Verify {
+        packages,
+        cyclic,
+        failed,
+    };
+
+    libraries::for_each(&mut verify);
+
+    if verify.failed {
+        std::process::exit(1);
+    }
+}
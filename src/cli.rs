@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::{City, CityGraph, Distance, Package, Weight};
+
+// Options shared by the binaries working on the cities and the dependency
+// tree.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub cities_path: PathBuf,
+    // Number of synthetic cities generated instead of loading the dataset.
+    pub synthetic: Option<usize>,
+    pub seed: u64,
+    // Empty if no weight was given, leaving the default to the binary.
+    pub weights: Vec<Weight>,
+    // Number of random start and target pairs for the bidirectional search.
+    pub pairs: usize,
+    pub tree_path: PathBuf,
+    pub start: String,
+    pub target: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            cities_path: crate::cities_path(),
+            synthetic: None,
+            seed: 0,
+            weights: Vec::new(),
+            pairs: crate::RANDOM_PAIRS,
+            tree_path: crate::tree_path(),
+            start: String::from(crate::DIJKSTRA_START),
+            target: String::from(crate::DIJKSTRA_TARGET),
+        }
+    }
+}
+
+impl Options {
+    pub const USAGE: &'static str = "[--cities PATH | --synthetic COUNT [--seed SEED]] [--start CITY] [--target CITY] [--weight NAME]... [--pairs COUNT] [--tree PATH]";
+
+    // Takes the value of the option from the arguments if it is one of the
+    // shared options. Returns false otherwise.
+    pub fn parse(
+        &mut self,
+        option: &str,
+        args: &mut impl Iterator<Item = String>,
+        usage: fn() -> !,
+    ) -> bool {
+        match option {
+            "--cities" => self.cities_path = value(args, usage),
+            "--synthetic" => self.synthetic = Some(value(args, usage)),
+            "--seed" => self.seed = value(args, usage),
+            "--start" => self.start = value(args, usage),
+            "--target" => self.target = value(args, usage),
+            "--weight" => {
+                let name = value::<String>(args, usage);
+                let weight = Weight::ALL
+                    .into_iter()
+                    .find(|w| w.name() == name)
+                    .unwrap_or_else(|| usage());
+                self.weights.push(weight);
+            }
+            "--pairs" => self.pairs = value(args, usage),
+            "--tree" => self.tree_path = value(args, usage),
+            _ => return false,
+        }
+
+        true
+    }
+
+    pub fn cities(&self) -> Result<Vec<City>, String> {
+        load_cities(&self.cities_path, self.synthetic, self.seed)
+    }
+
+    // Indices of the start and target cities in the graph.
+    pub fn endpoints<D: Distance>(&self, cities: &CityGraph<D>) -> Result<(usize, usize), String> {
+        endpoints(cities, &self.start, &self.target)
+    }
+
+    pub fn random_pairs(&self, n: usize) -> Vec<(usize, usize)> {
+        crate::synthetic::pairs(n, self.pairs, self.seed)
+    }
+
+    pub fn tree(&self) -> Result<Vec<Package>, String> {
+        load_tree(&self.tree_path)
+    }
+}
+
+// Parses the next argument as the value of an option.
+pub fn value<T: FromStr>(args: &mut impl Iterator<Item = String>, usage: fn() -> !) -> T {
+    args.next()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| usage())
+}
+
+// Each of the following returns the message of the error, prefixed with the
+// path of the file if any, for the binaries to report before they exit.
+
+pub fn load_cities(path: &Path, synthetic: Option<usize>, seed: u64) -> Result<Vec<City>, String> {
+    if let Some(count) = synthetic {
+        return Ok(crate::synthetic::cities(count, seed));
+    }
+
+    let loaded = crate::load_cities_from(path, true)
+        .map_err(|error| format!("{}: {error}", path.display()))?;
+
+    for warning in &loaded.warnings {
+        eprintln!("{}: skipping {warning}", path.display());
+    }
+
+    Ok(loaded.cities)
+}
+
+pub fn endpoints<D: Distance>(
+    cities: &CityGraph<D>,
+    start: &str,
+    target: &str,
+) -> Result<(usize, usize), String> {
+    let position = |name: &str| {
+        cities.position(name).ok_or_else(|| {
+            format!(
+                "no city named {name:?} among {} cities",
+                cities.cities.len()
+            )
+        })
+    };
+
+    Ok((position(start)?, position(target)?))
+}
+
+pub fn load_tree(path: &Path) -> Result<Vec<Package>, String> {
+    crate::load_tree_from(path).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+// The packages with an injected cycle and the cycle, or `None` with a message
+// if the tree has no chain to close, so that the dependency cycle exercise is
+// skipped.
+pub fn inject_cycle(packages: &[Package]) -> Option<(Vec<Package>, Vec<String>)> {
+    let mut packages = packages.to_vec();
+
+    let Some(cycle) = crate::inject_cycle(&mut packages, crate::CYCLE_LENGTH) else {
+        eprintln!(
+            "skipping the dependency cycle: the dependency tree has no chain of {} packages",
+            crate::CYCLE_LENGTH
+        );
+        return None;
+    };
+
+    Some((packages, cycle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::city;
+
+    #[test]
+    fn endpoints_report_missing_city() {
+        let cities = CityGraph::<f32>::new(vec![city(1, 0.0, 0.0), city(2, 1.0, 0.0)]);
+
+        assert_eq!(endpoints(&cities, "city 2", "city 1"), Ok((1, 0)));
+        assert_eq!(
+            endpoints(&cities, "city 1", "city 3"),
+            Err(String::from("no city named \"city 3\" among 2 cities"))
+        );
+    }
+
+    #[test]
+    fn load_tree_reports_path() {
+        let error = load_tree(Path::new("missing/tree.json")).unwrap_err();
+        assert!(error.starts_with("missing/tree.json: "), "{error}");
+    }
+}
@@ -0,0 +1,230 @@
+use std::{any::Any, fmt, ops::Add};
+
+use num_traits::{Unsigned, Zero};
+use ordered_float::OrderedFloat;
+use serde::Serialize;
+
+use crate::{City, UFloat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Weight {
+    F32,
+    F64,
+    Meters,
+    Millimeters,
+}
+
+impl Weight {
+    pub const ALL: [Weight; 4] = [
+        Weight::F32,
+        Weight::F64,
+        Weight::Meters,
+        Weight::Millimeters,
+    ];
+    // Used by the binaries unless given otherwise, one float and one integer.
+    pub const DEFAULT: [Weight; 2] = [Weight::F32, Weight::Meters];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Weight::F32 => "f32",
+            Weight::F64 => "f64",
+            Weight::Meters => "meters",
+            Weight::Millimeters => "millimeters",
+        }
+    }
+}
+
+// Type of the edge weights in the shortest paths exercise. The libraries get
+// the weights in the type they support that is the closest to it.
+pub trait Distance:
+    Copy
+    + Default
+    + PartialOrd
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Send
+    + Sync
+    + 'static
+    + gryf::core::weight::Weight
+    + petgraph::algo::Measure
+{
+    const WEIGHT: Weight;
+    // Distance of unreachable cities.
+    const INFINITY: Self;
+    // Relative tolerance for comparing distances, accounting for different
+    // order of operations in the libraries. Zero for integers, which are
+    // summed exactly.
+    const TOLERANCE: f64;
+
+    // Totally ordered equivalent, for libraries requiring `Ord`.
+    type Ordered: Copy + Ord + Zero + From<Self> + Into<Self>;
+    // Equivalent implementing `num_traits::Unsigned`.
+    type Unsigned: Copy
+        + Ord
+        + Zero
+        + Unsigned
+        + Any
+        + TryFrom<Self, Error: fmt::Debug>
+        + Into<Self>;
+
+    fn between(a: &City, b: &City) -> Self;
+
+    // Lower bound of the distance between the cities over the graph, used as
+    // the heuristic of A*.
+    fn estimate(a: &City, b: &City) -> Self {
+        Self::between(a, b)
+    }
+
+    // In kilometers, the unit of `City::dist`.
+    fn to_f64(self) -> f64;
+    fn from_f64(x: f64) -> Self;
+}
+
+impl Distance for f32 {
+    const WEIGHT: Weight = Weight::F32;
+    const INFINITY: Self = f32::INFINITY;
+    const TOLERANCE: f64 = 1e-4;
+
+    type Ordered = OrderedFloat<f32>;
+    type Unsigned = UFloat<f32>;
+
+    fn between(a: &City, b: &City) -> Self {
+        a.dist(b)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.into()
+    }
+
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+}
+
+impl Distance for f64 {
+    const WEIGHT: Weight = Weight::F64;
+    const INFINITY: Self = f64::INFINITY;
+    const TOLERANCE: f64 = 1e-9;
+
+    type Ordered = OrderedFloat<f64>;
+    type Unsigned = UFloat<f64>;
+
+    fn between(a: &City, b: &City) -> Self {
+        a.dist_f64(b)
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+}
+
+// Fixed-point distance, an integer number of meters. Edge weights are rounded
+// up to whole meters, so the distances are exact sums of them regardless of the
+// order of operations.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Meters(pub u32);
+
+// Like `Meters`, but an integer number of millimeters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Millimeters(pub u64);
+
+macro_rules! impl_fixed_point {
+    ($name:ident, $int:ty, $weight:expr, $per_km:expr) => {
+        impl From<$int> for $name {
+            fn from(x: $int) -> Self {
+                $name(x)
+            }
+        }
+
+        impl From<$name> for $int {
+            fn from(x: $name) -> Self {
+                x.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            // Saturates, so that infinity stays infinite.
+            fn add(self, rhs: Self) -> Self::Output {
+                $name(self.0.saturating_add(rhs.0))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        // The largest value stands for infinity, as it does for integers.
+        impl gryf::core::weight::Weight for $name {
+            type Ord = Self;
+
+            fn zero() -> Self {
+                $name(0)
+            }
+
+            fn inf() -> Self {
+                $name(<$int>::MAX)
+            }
+
+            fn is_unsigned() -> bool {
+                true
+            }
+        }
+
+        impl Distance for $name {
+            const WEIGHT: Weight = $weight;
+            const INFINITY: Self = $name(<$int>::MAX);
+            const TOLERANCE: f64 = 0.0;
+
+            type Ordered = $int;
+            type Unsigned = $int;
+
+            // Rounded up from the distance in f64, so that no path is shorter
+            // than the rounded down `estimate`.
+            fn between(a: &City, b: &City) -> Self {
+                $name((a.dist_f64(b) * $per_km).ceil() as $int)
+            }
+
+            // Rounded down, so that it does not exceed any path between the
+            // cities.
+            fn estimate(a: &City, b: &City) -> Self {
+                $name((a.dist_f64(b) * $per_km).floor() as $int)
+            }
+
+            fn to_f64(self) -> f64 {
+                self.0 as f64 / $per_km
+            }
+
+            fn from_f64(x: f64) -> Self {
+                $name((x * $per_km).round() as $int)
+            }
+        }
+    };
+}
+
+impl_fixed_point!(Meters, u32, Weight::Meters, 1e3);
+impl_fixed_point!(Millimeters, u64, Weight::Millimeters, 1e6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_point_add_saturates() {
+        assert_eq!(Meters::INFINITY + Meters(1), Meters::INFINITY);
+        assert_eq!(
+            Millimeters(1) + Millimeters::INFINITY,
+            Millimeters::INFINITY
+        );
+        assert_eq!(Meters(1) + Meters(2), Meters(3));
+    }
+}
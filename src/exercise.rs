@@ -0,0 +1,895 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    reference::{self, PathError},
+    CityGraph, Distance, GraphLibrary, Package, Weight,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Exercise {
+    ShortestPaths,
+    Astar,
+    Bidirectional,
+    SpanningForest,
+    ConnectedComponents,
+    TopologicalOrder,
+    TopologicalLevels,
+    DependencyCycle,
+}
+
+impl Exercise {
+    pub const ALL: [Exercise; 8] = [
+        Exercise::ShortestPaths,
+        Exercise::Astar,
+        Exercise::Bidirectional,
+        Exercise::SpanningForest,
+        Exercise::ConnectedComponents,
+        Exercise::TopologicalOrder,
+        Exercise::TopologicalLevels,
+        Exercise::DependencyCycle,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Exercise::ShortestPaths => "shortest-paths",
+            Exercise::Astar => "astar",
+            Exercise::Bidirectional => "bidirectional",
+            Exercise::SpanningForest => "spanning-forest",
+            Exercise::ConnectedComponents => "connected-components",
+            Exercise::TopologicalOrder => "topological-order",
+            Exercise::TopologicalLevels => "topological-levels",
+            Exercise::DependencyCycle => "dependency-cycle",
+        }
+    }
+
+    pub fn phases(self) -> &'static [Phase] {
+        match self {
+            Exercise::ShortestPaths => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::FindStartTarget,
+                Phase::Dijkstra,
+                Phase::DijkstraWithGoal,
+                Phase::ShortestPath,
+            ],
+            Exercise::Astar => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::FindStartTarget,
+                Phase::Astar,
+            ],
+            Exercise::Bidirectional => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::DijkstraWithGoal,
+                Phase::Bidirectional,
+            ],
+            Exercise::SpanningForest => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::SpanningForest,
+            ],
+            Exercise::ConnectedComponents => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::ConnectedComponents,
+            ],
+            Exercise::TopologicalOrder => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::TopologicalSort,
+            ],
+            Exercise::TopologicalLevels => &[
+                Phase::AddVertices,
+                Phase::ConnectVertices,
+                Phase::TopologicalLevels,
+            ],
+            Exercise::DependencyCycle => {
+                &[Phase::AddVertices, Phase::ConnectVertices, Phase::FindCycle]
+            }
+        }
+    }
+
+    // Suffix of the example files showcasing the exercise, or `None` if the
+    // exercise is showcased by the methods of the library adapters.
+    pub fn example(self) -> Option<&'static str> {
+        match self {
+            Exercise::ShortestPaths => Some("dijkstra"),
+            Exercise::TopologicalOrder => Some("toposort"),
+            _ => None,
+        }
+    }
+
+    // Method of `GraphLibrary` showcasing the exercise instead of the
+    // examples, or `None` if it has examples or is only run against the
+    // reference.
+    pub fn method(self) -> Option<&'static str> {
+        match self {
+            Exercise::Astar => Some("astar"),
+            Exercise::SpanningForest => Some("spanning_forest"),
+            Exercise::ConnectedComponents => Some("connected_components"),
+            Exercise::TopologicalLevels => Some("topological_levels"),
+            Exercise::DependencyCycle => Some("find_cycle"),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Exercise::ShortestPaths => "shortest paths",
+            Exercise::Astar => "A* search",
+            Exercise::Bidirectional => "bidirectional search",
+            Exercise::SpanningForest => "minimum spanning forest",
+            Exercise::ConnectedComponents => "connected components",
+            Exercise::TopologicalOrder => "topological order",
+            Exercise::TopologicalLevels => "topological levels",
+            Exercise::DependencyCycle => "dependency cycle",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Phase {
+    AddVertices,
+    ConnectVertices,
+    FindStartTarget,
+    Dijkstra,
+    DijkstraWithGoal,
+    ShortestPath,
+    Astar,
+    // Reference implementation, the same for all libraries.
+    Bidirectional,
+    SpanningForest,
+    ConnectedComponents,
+    TopologicalSort,
+    TopologicalLevels,
+    FindCycle,
+}
+
+impl Phase {
+    pub fn is_algorithm(self) -> bool {
+        matches!(
+            self,
+            Phase::Dijkstra
+                | Phase::DijkstraWithGoal
+                | Phase::ShortestPath
+                | Phase::Astar
+                | Phase::SpanningForest
+                | Phase::ConnectedComponents
+                | Phase::TopologicalSort
+                | Phase::TopologicalLevels
+                | Phase::FindCycle
+        )
+    }
+
+    // Shown instead of the duration if the phase was not run, as only
+    // algorithms may be missing in a library.
+    pub fn missing(self) -> &'static str {
+        if self.is_algorithm() {
+            "not available"
+        } else {
+            "not applicable"
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::AddVertices => "adding vertices",
+            Phase::ConnectVertices => "connecting vertices",
+            Phase::FindStartTarget => "finding start and target",
+            Phase::Dijkstra => "dijkstra (without goal)",
+            Phase::DijkstraWithGoal => "dijkstra (with goal)",
+            Phase::ShortestPath => "shortest path",
+            Phase::Astar => "astar",
+            Phase::Bidirectional => "bidirectional dijkstra (reference)",
+            Phase::SpanningForest => "minimum spanning forest",
+            Phase::ConnectedComponents => "connected components",
+            Phase::TopologicalSort => "topological sort",
+            Phase::TopologicalLevels => "topological levels",
+            Phase::FindCycle => "finding cycle",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Output {
+    Vertices(usize),
+    Edges(usize),
+    // Formatted in the precision of the weights.
+    Distance(String),
+    // Names of the cities from the start to the target.
+    Path {
+        distance: String,
+        cities: Vec<String>,
+    },
+    // Number of distinct vertices taken from the queue.
+    Search {
+        distance: String,
+        visited: usize,
+    },
+    Pairs {
+        pairs: usize,
+        reachable: usize,
+    },
+    // Speedup of the bidirectional search over the library for pairs in each
+    // range of distances, as `(range, pairs, speedup)`.
+    Speedup(Vec<(String, usize, f64)>),
+    // Total weight of the minimum spanning forest and the number of its trees,
+    // that is, of the connected components.
+    Forest {
+        weight: String,
+        components: usize,
+    },
+    // Number of the components, size of the largest one and sizes of the
+    // components of the looked up cities.
+    Components {
+        count: usize,
+        largest: usize,
+        cities: Vec<(String, usize)>,
+    },
+    Sorted(Vec<String>),
+    // Packages that can be built in parallel, level by level.
+    Levels(Vec<Vec<String>>),
+    // Each package is a dependency of the next one.
+    Cycle(Vec<String>),
+}
+
+impl Output {
+    fn distance<D: Distance>(distance: D) -> Self {
+        Output::Distance(format_distance(distance))
+    }
+
+    // Bare value without the label, used in exported results.
+    pub fn value(&self) -> String {
+        match self {
+            Output::Vertices(n) | Output::Edges(n) => n.to_string(),
+            Output::Distance(distance) => distance.clone(),
+            Output::Path { cities, .. } => cities.join(" -> "),
+            Output::Search { distance, visited } => format!("{distance} {visited}"),
+            Output::Pairs { pairs, reachable } => format!("{pairs} {reachable}"),
+            Output::Speedup(ranges) => ranges
+                .iter()
+                .map(|(range, pairs, speedup)| format!("{range} {pairs} {speedup:.2}"))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Output::Forest { weight, components } => format!("{weight} {components}"),
+            Output::Components {
+                count,
+                largest,
+                cities,
+            } => {
+                let cities = cities
+                    .iter()
+                    .map(|(name, size)| format!(" | {name} {size}"))
+                    .collect::<String>();
+                format!("{count} {largest}{cities}")
+            }
+            Output::Sorted(sorted) | Output::Cycle(sorted) => sorted.join(" "),
+            Output::Levels(levels) => levels
+                .iter()
+                .map(|level| level.join(" "))
+                .collect::<Vec<_>>()
+                .join(" | "),
+        }
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Output::Vertices(n) => write!(f, "vertices = {n}"),
+            Output::Edges(n) => write!(f, "edges = {n}"),
+            Output::Distance(distance) => write!(f, "distance = {distance}"),
+            Output::Path { distance, cities } => {
+                write!(f, "distance = {distance}, cities = {}", cities.len())
+            }
+            Output::Search { distance, visited } => {
+                write!(f, "distance = {distance}, visited = {visited}")
+            }
+            Output::Pairs { pairs, reachable } => {
+                write!(f, "pairs = {pairs}, reachable = {reachable}")
+            }
+            Output::Speedup(ranges) => {
+                let ranges = ranges
+                    .iter()
+                    .map(|(range, pairs, speedup)| format!("{speedup:.2} for {range} ({pairs})"))
+                    .collect::<Vec<_>>();
+                write!(f, "speedup = {}", ranges.join(", "))
+            }
+            Output::Forest { weight, components } => {
+                write!(f, "weight = {weight}, components = {components}")
+            }
+            Output::Components {
+                count,
+                largest,
+                cities,
+            } => {
+                write!(f, "components = {count}, largest = {largest}")?;
+                for (name, size) in cities.iter() {
+                    write!(f, ", {name}: {size}")?;
+                }
+                Ok(())
+            }
+            Output::Sorted(sorted) => write!(f, "{sorted:?}"),
+            Output::Levels(levels) => {
+                let width = levels.iter().map(Vec::len).max().unwrap_or(0);
+                write!(f, "critical path = {}, width = {width}", levels.len())
+            }
+            Output::Cycle(cycle) => {
+                write!(f, "cycle = {}", cycle.join(" -> "))?;
+                match cycle.first() {
+                    Some(first) => write!(f, " -> {first}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+// Unreachable target is infinite also for integer distances.
+fn format_distance<D: Distance>(distance: D) -> String {
+    if distance == D::INFINITY {
+        f32::INFINITY.to_string()
+    } else {
+        distance.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseRun {
+    pub phase: Phase,
+    // `None` if the library does not provide the algorithm, or does not add
+    // the vertices on their own.
+    pub duration: Option<Duration>,
+    pub output: Option<Output>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub library: &'static str,
+    pub exercise: Exercise,
+    // Type of the edge weights, if the graph is weighted.
+    pub weight: Option<Weight>,
+    pub vertices: usize,
+    pub edges: usize,
+    pub phases: Vec<PhaseRun>,
+}
+
+impl Run {
+    fn new<L: GraphLibrary>(exercise: Exercise) -> Self {
+        Self {
+            library: L::NAME,
+            exercise,
+            weight: None,
+            vertices: 0,
+            edges: 0,
+            phases: Vec::new(),
+        }
+    }
+
+    fn phase(&mut self, phase: Phase, duration: Option<Duration>, output: Option<Output>) {
+        self.phases.push(PhaseRun {
+            phase,
+            duration,
+            output,
+        });
+    }
+}
+
+// Each phase on its own line followed by its output, the way the examples print
+// them.
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for run in self.phases.iter() {
+            match run.duration {
+                Some(duration) => writeln!(f, "{} took {:?}", run.phase.label(), duration)?,
+                None => writeln!(f, "{} {}", run.phase.label(), run.phase.missing())?,
+            }
+
+            if let Some(output) = &run.output {
+                writeln!(f, "{output}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_supported<L: GraphLibrary, T>(phase: Phase, result: &Option<T>) {
+    debug_assert_eq!(
+        result.is_some(),
+        L::ALGORITHMS.contains(&phase),
+        "{} does not match its record of supported algorithms for {}",
+        L::NAME,
+        phase.label()
+    );
+}
+
+// Adds the cities to a new graph and connects them, timing both phases (only
+// the latter if the library does not add the vertices on their own).
+fn build_cities<'a, L: GraphLibrary, D: Distance>(
+    input: &'a CityGraph<D>,
+    run: &mut Run,
+) -> L::Cities<'a, D> {
+    let started = Instant::now();
+    let graph = L::add_cities(input);
+    let elapsed = started.elapsed();
+
+    run.vertices = L::cities_size(&graph).0;
+    let output = Output::Vertices(run.vertices);
+    run.phase(
+        Phase::AddVertices,
+        L::ADDS_VERTICES.then_some(elapsed),
+        Some(output),
+    );
+
+    let started = Instant::now();
+    let graph = L::connect_cities(graph, input);
+    let elapsed = started.elapsed();
+
+    run.edges = L::cities_size(&graph).1;
+    let output = Output::Edges(run.edges);
+    run.phase(Phase::ConnectVertices, Some(elapsed), Some(output));
+
+    graph
+}
+
+// Adds the packages to a new graph and connects them, timing both phases (only
+// the latter if the library does not add the vertices on their own).
+fn build_packages<'a, L: GraphLibrary>(packages: &'a [Package], run: &mut Run) -> L::Packages<'a> {
+    let started = Instant::now();
+    let graph = L::add_packages(packages);
+    let elapsed = started.elapsed();
+
+    run.vertices = L::packages_size(&graph).0;
+    let output = Output::Vertices(run.vertices);
+    run.phase(
+        Phase::AddVertices,
+        L::ADDS_VERTICES.then_some(elapsed),
+        Some(output),
+    );
+
+    let started = Instant::now();
+    let graph = L::connect_packages(graph);
+    let elapsed = started.elapsed();
+
+    run.edges = L::packages_size(&graph).1;
+    let output = Output::Edges(run.edges);
+    run.phase(Phase::ConnectVertices, Some(elapsed), Some(output));
+
+    graph
+}
+
+// The start and target are indices of cities in the input graph.
+pub fn shortest_paths<L: GraphLibrary, D: Distance>(
+    input: &CityGraph<D>,
+    start: usize,
+    target: usize,
+) -> Run {
+    let mut run = Run::new::<L>(Exercise::ShortestPaths);
+    run.weight = Some(D::WEIGHT);
+
+    let graph = build_cities::<L, D>(input, &mut run);
+
+    let started = Instant::now();
+    let found = L::find_cities(
+        &graph,
+        &input.cities[start].name,
+        &input.cities[target].name,
+    );
+    run.phase(Phase::FindStartTarget, Some(started.elapsed()), None);
+
+    let Some((start_id, target_id)) = found else {
+        panic!("{} did not find the start or target city", L::NAME);
+    };
+
+    let started = Instant::now();
+    let result = L::dijkstra(&graph, start_id, target_id);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::Dijkstra, &result);
+
+    let output = result.map(Output::distance);
+    run.phase(Phase::Dijkstra, result.map(|_| elapsed), output);
+
+    let started = Instant::now();
+    let result = L::dijkstra_with_goal(&graph, start_id, target_id);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::DijkstraWithGoal, &result);
+
+    let output = result.map(Output::distance);
+    run.phase(Phase::DijkstraWithGoal, result.map(|_| elapsed), output);
+
+    let started = Instant::now();
+    let result = L::shortest_path(&graph, start_id, target_id);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::ShortestPath, &result);
+
+    if let Some(path) = &result {
+        match reference::verify_path(input, start, target, path) {
+            // The distance is not exact if the weights are converted.
+            Err(PathError::Distance { .. }) if !L::WEIGHTS.contains(&D::WEIGHT) => {}
+            Err(error) => panic!("{} produced invalid shortest path: {error}", L::NAME),
+            Ok(()) => {}
+        }
+    }
+
+    let duration = result.as_ref().map(|_| elapsed);
+    let output = result.map(|path| {
+        let names = input
+            .cities
+            .iter()
+            .map(|city| (city.id, city.name.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let distance = format_distance(path.distance);
+        let cities = path.cities.iter().map(|id| names[id].clone()).collect();
+
+        Output::Path { distance, cities }
+    });
+    run.phase(Phase::ShortestPath, duration, output);
+
+    run
+}
+
+pub fn astar<L: GraphLibrary, D: Distance>(
+    input: &CityGraph<D>,
+    start: usize,
+    target: usize,
+) -> Run {
+    let mut run = Run::new::<L>(Exercise::Astar);
+    run.weight = Some(D::WEIGHT);
+
+    let graph = build_cities::<L, D>(input, &mut run);
+
+    let started = Instant::now();
+    let found = L::find_cities(
+        &graph,
+        &input.cities[start].name,
+        &input.cities[target].name,
+    );
+    run.phase(Phase::FindStartTarget, Some(started.elapsed()), None);
+
+    let Some((start_id, target_id)) = found else {
+        panic!("{} did not find the start or target city", L::NAME);
+    };
+
+    let started = Instant::now();
+    let result = L::astar(&graph, start_id, target_id);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::Astar, &result);
+
+    let duration = result.map(|_| elapsed);
+    let output = result.map(|(distance, visited)| Output::Search {
+        distance: format_distance(distance),
+        visited,
+    });
+    run.phase(Phase::Astar, duration, output);
+
+    run
+}
+
+// Upper bounds of the distance ranges in kilometers, in which the speedup of
+// the bidirectional search is reported.
+const SPEEDUP_RANGES: [f64; 3] = [1000.0, 3000.0, 6000.0];
+
+pub fn bidirectional<L: GraphLibrary, D: Distance>(
+    input: &CityGraph<D>,
+    pairs: &[(usize, usize)],
+) -> Run {
+    let mut run = Run::new::<L>(Exercise::Bidirectional);
+    run.weight = Some(D::WEIGHT);
+
+    let graph = build_cities::<L, D>(input, &mut run);
+
+    // Each pair is timed separately to compare them by distance.
+    let result = pairs
+        .iter()
+        .map(|&(start, target)| {
+            let (start, target) = (L::city_id(&graph, start), L::city_id(&graph, target));
+
+            let started = Instant::now();
+            let distance = L::dijkstra_with_goal(&graph, start, target)?;
+            Some((distance, started.elapsed()))
+        })
+        .collect::<Option<Vec<_>>>();
+
+    check_supported::<L, _>(Phase::DijkstraWithGoal, &result);
+
+    let reachable = |distances: &[(D, Duration)]| {
+        distances
+            .iter()
+            .filter(|(distance, _)| *distance != D::INFINITY)
+            .count()
+    };
+
+    let duration = result.as_ref().map(|r| r.iter().map(|(_, d)| *d).sum());
+    let output = result.as_ref().map(|r| Output::Pairs {
+        pairs: pairs.len(),
+        reachable: reachable(r),
+    });
+    run.phase(Phase::DijkstraWithGoal, duration, output);
+
+    let neighbors = reference::graph_neighbors(input);
+    let reference = pairs
+        .iter()
+        .map(|&(start, target)| {
+            let started = Instant::now();
+            let (distance, _) = reference::bidirectional_dijkstra(&neighbors, start, target);
+            (distance, started.elapsed())
+        })
+        .collect::<Vec<_>>();
+
+    let duration = reference.iter().map(|(_, d)| *d).sum();
+    let output = match &result {
+        Some(result) => {
+            for (i, (&(expected, _), &(actual, _))) in reference.iter().zip(result).enumerate() {
+                // The distance is not exact if the weights are converted.
+                if reference::distances_differ(expected, actual) && L::WEIGHTS.contains(&D::WEIGHT)
+                {
+                    panic!(
+                        "{} found distance {actual} between cities #{} and #{}, expected {expected}",
+                        L::NAME,
+                        pairs[i].0,
+                        pairs[i].1
+                    );
+                }
+            }
+
+            speedup(&reference, result)
+        }
+        None => Output::Pairs {
+            pairs: pairs.len(),
+            reachable: reachable(&reference),
+        },
+    };
+    run.phase(Phase::Bidirectional, Some(duration), Some(output));
+
+    run
+}
+
+// Ratio of the total times of the library and the reference in each range of
+// the distances. Unreachable targets are reported separately.
+fn speedup<D: Distance>(reference: &[(D, Duration)], library: &[(D, Duration)]) -> Output {
+    let range = |distance: D| {
+        if distance == D::INFINITY {
+            return SPEEDUP_RANGES.len() + 1;
+        }
+
+        let distance = distance.to_f64();
+        SPEEDUP_RANGES
+            .iter()
+            .position(|&bound| distance < bound)
+            .unwrap_or(SPEEDUP_RANGES.len())
+    };
+
+    let mut totals = vec![(0, Duration::ZERO, Duration::ZERO); SPEEDUP_RANGES.len() + 2];
+
+    for (&(distance, reference), &(_, library)) in reference.iter().zip(library) {
+        let total = &mut totals[range(distance)];
+        total.0 += 1;
+        total.1 += reference;
+        total.2 += library;
+    }
+
+    let label = |i: usize| match i {
+        0 => format!("< {} km", SPEEDUP_RANGES[0]),
+        i if i < SPEEDUP_RANGES.len() => {
+            format!("{}-{} km", SPEEDUP_RANGES[i - 1], SPEEDUP_RANGES[i])
+        }
+        i if i == SPEEDUP_RANGES.len() => format!(">= {} km", SPEEDUP_RANGES[i - 1]),
+        _ => "unreachable".to_string(),
+    };
+
+    let ranges = totals
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (pairs, _, _))| *pairs > 0)
+        .map(|(i, (pairs, reference, library))| {
+            let speedup = library.as_secs_f64() / reference.as_secs_f64();
+            (label(i), pairs, speedup)
+        })
+        .collect();
+
+    Output::Speedup(ranges)
+}
+
+pub fn spanning_forest<L: GraphLibrary, D: Distance>(input: &CityGraph<D>) -> Run {
+    let mut run = Run::new::<L>(Exercise::SpanningForest);
+    run.weight = Some(D::WEIGHT);
+
+    let graph = build_cities::<L, D>(input, &mut run);
+
+    let started = Instant::now();
+    let result = L::spanning_forest(&graph);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::SpanningForest, &result);
+
+    if let Some((weight, components)) = result {
+        let (expected, expected_components) = reference::kruskal(input);
+
+        if components != expected_components {
+            panic!(
+                "{} found spanning forest with {components} components, expected {expected_components}",
+                L::NAME
+            );
+        }
+
+        // The weight is not exact if the weights are converted.
+        if reference::distances_differ(expected, weight) && L::WEIGHTS.contains(&D::WEIGHT) {
+            panic!(
+                "{} found spanning forest of weight {weight}, expected {expected}",
+                L::NAME
+            );
+        }
+    }
+
+    let duration = result.map(|_| elapsed);
+    let output = result.map(|(weight, components)| Output::Forest {
+        weight: weight.to_string(),
+        components,
+    });
+    run.phase(Phase::SpanningForest, duration, output);
+
+    run
+}
+
+// The graph is not weighted in this exercise, so only f32 weights are used.
+pub fn connected_components<L: GraphLibrary>(input: &CityGraph) -> Run {
+    let mut run = Run::new::<L>(Exercise::ConnectedComponents);
+
+    let graph = build_cities::<L, f32>(input, &mut run);
+
+    let started = Instant::now();
+    let result = L::connected_components(&graph);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::ConnectedComponents, &result);
+
+    if let Some(labels) = &result {
+        let expected = reference::connected_components(input);
+
+        if let Some(i) = reference::components_mismatch(&expected, labels) {
+            panic!(
+                "{} put city {} (#{i}) into a wrong component",
+                L::NAME,
+                input.cities[i].name
+            );
+        }
+    }
+
+    let duration = result.as_ref().map(|_| elapsed);
+    let output = result.map(|labels| {
+        let mut sizes = HashMap::new();
+        for &label in labels.iter() {
+            *sizes.entry(label).or_insert(0) += 1;
+        }
+
+        // Cities missing in the data are skipped.
+        let cities = crate::COMPONENT_CITIES
+            .iter()
+            .filter_map(|&name| {
+                let i = input.position(name)?;
+                Some((name.to_string(), sizes[&labels[i]]))
+            })
+            .collect();
+
+        Output::Components {
+            count: sizes.len(),
+            largest: sizes.values().copied().max().unwrap_or(0),
+            cities,
+        }
+    });
+    run.phase(Phase::ConnectedComponents, duration, output);
+
+    run
+}
+
+// Runs one of the exercises on the city graph.
+pub fn on_cities<L: GraphLibrary, D: Distance>(
+    exercise: Exercise,
+    input: &CityGraph<D>,
+    (start, target): (usize, usize),
+    pairs: &[(usize, usize)],
+) -> Run {
+    match exercise {
+        Exercise::ShortestPaths => shortest_paths::<L, D>(input, start, target),
+        Exercise::Astar => astar::<L, D>(input, start, target),
+        Exercise::Bidirectional => bidirectional::<L, D>(input, pairs),
+        Exercise::SpanningForest => spanning_forest::<L, D>(input),
+        _ => panic!("{} is not an exercise on cities", exercise.label()),
+    }
+}
+
+pub fn topological_order<L: GraphLibrary>(packages: &[Package]) -> Run {
+    let mut run = Run::new::<L>(Exercise::TopologicalOrder);
+
+    let graph = build_packages::<L>(packages, &mut run);
+
+    let started = Instant::now();
+    let result = L::toposort(&graph);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::TopologicalSort, &result);
+
+    if let Some(sorted) = &result {
+        if let Err(error) = reference::verify_topological_order(packages, sorted) {
+            panic!("{} produced invalid topological order: {error}", L::NAME);
+        }
+    }
+
+    let duration = result.as_ref().map(|_| elapsed);
+    let output =
+        result.map(|sorted| Output::Sorted(sorted.into_iter().map(String::from).collect()));
+    run.phase(Phase::TopologicalSort, duration, output);
+
+    run
+}
+
+pub fn topological_levels<L: GraphLibrary>(packages: &[Package]) -> Run {
+    let mut run = Run::new::<L>(Exercise::TopologicalLevels);
+
+    let graph = build_packages::<L>(packages, &mut run);
+
+    let started = Instant::now();
+    let result = L::topological_levels(&graph);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::TopologicalLevels, &result);
+
+    if let Some(levels) = &result {
+        if let Err(error) = reference::verify_topological_levels(packages, levels) {
+            panic!("{} produced invalid topological levels: {error}", L::NAME);
+        }
+    }
+
+    let duration = result.as_ref().map(|_| elapsed);
+    let output = result.map(|levels| {
+        Output::Levels(
+            levels
+                .into_iter()
+                .map(|level| level.into_iter().map(String::from).collect())
+                .collect(),
+        )
+    });
+    run.phase(Phase::TopologicalLevels, duration, output);
+
+    run
+}
+
+// The packages are expected to contain a cycle, see `inject_cycle`.
+pub fn dependency_cycle<L: GraphLibrary>(packages: &[Package]) -> Run {
+    let mut run = Run::new::<L>(Exercise::DependencyCycle);
+
+    let graph = build_packages::<L>(packages, &mut run);
+
+    let started = Instant::now();
+    let result = L::find_cycle(&graph);
+    let elapsed = started.elapsed();
+
+    check_supported::<L, _>(Phase::FindCycle, &result);
+
+    if let Some(cycle) = &result {
+        if let Err(error) = reference::verify_cycle(packages, cycle) {
+            panic!("{} produced invalid cycle: {error}", L::NAME);
+        }
+    }
+
+    let duration = result.as_ref().map(|_| elapsed);
+    let output = result.map(|cycle| Output::Cycle(cycle.into_iter().map(String::from).collect()));
+    run.phase(Phase::FindCycle, duration, output);
+
+    run
+}
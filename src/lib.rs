@@ -1,46 +1,142 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
 
+use flate2::read::MultiGzDecoder;
 use serde::Deserialize;
 
+use exercise::Phase;
+
+pub mod cli;
+mod distance;
+pub mod exercise;
+pub mod libraries;
+pub mod reference;
+pub mod results;
+pub mod spatial;
+pub mod stats;
+pub mod synthetic;
 mod unsigned_float;
 
-pub use unsigned_float::UF32;
+pub use distance::{Distance, Meters, Millimeters, Weight};
+pub use unsigned_float::{
+    ParseUF32Error, ParseUF64Error, ParseUFloatError, Primitive, UF32Error, UF64Error, UFloat,
+    UFloatError, UF32, UF64,
+};
 
 pub const DIJKSTRA_START: &str = "Cape Town";
 pub const DIJKSTRA_TARGET: &str = "Murmansk";
+// Number of random start and target pairs in the bidirectional search exercise.
+pub const RANDOM_PAIRS: usize = 100;
+// Cities whose components are looked up in the connected components exercise,
+// the ends of the shortest paths and two remote islands.
+pub const COMPONENT_CITIES: &[&str] = &[DIJKSTRA_START, DIJKSTRA_TARGET, "Honolulu", "Reykjavík"];
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct City {
     pub id: u64,
     pub name: String,
     pub pop: u64,
-    pub lat: f32,
-    pub lon: f32,
+    pub lat: f64,
+    pub lon: f64,
 }
 
 impl City {
     pub const MAX_DISTANCE: f32 = 300.0;
+    pub const EARTH_RADIUS: f32 = 6371.0;
 
+    // Computed entirely in f32, including the coordinates.
     pub fn dist(&self, other: &Self) -> f32 {
-        const EARTH_RADIUS: f32 = 6371.0;
+        haversine(
+            (self.lat as f32, self.lon as f32),
+            (other.lat as f32, other.lon as f32),
+            Self::EARTH_RADIUS,
+        )
+    }
+
+    pub fn dist_f64(&self, other: &Self) -> f64 {
+        haversine(
+            (self.lat, self.lon),
+            (other.lat, other.lon),
+            f64::from(Self::EARTH_RADIUS),
+        )
+    }
+
+    // Connectivity is always decided in f32, so that the graph has the same
+    // edges regardless of the precision of the weights.
+    pub fn are_connected(&self, other: &Self) -> Option<f32> {
+        let d = self.dist(other);
+        (d <= Self::MAX_DISTANCE && d > 0.0).then_some(d)
+    }
+}
+
+fn haversine<T: num_traits::Float>((lat1, lon1): (T, T), (lat2, lon2): (T, T), radius: T) -> T {
+    let half = T::from(0.5).unwrap();
+    let two = T::from(2.0).unwrap();
 
-        let delta_lat = (other.lat - self.lat).to_radians();
-        let delta_lon = (other.lon - self.lon).to_radians();
+    let delta_lat = (lat2 - lat1).to_radians();
+    let delta_lon = (lon2 - lon1).to_radians();
 
-        let lat1 = self.lat.to_radians();
-        let lat2 = other.lat.to_radians();
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
 
-        let a = (delta_lat * 0.5).sin().powi(2)
-            + (delta_lon * 0.5).sin().powi(2) * lat1.cos() * lat2.cos();
+    let a = (delta_lat * half).sin().powi(2)
+        + (delta_lon * half).sin().powi(2) * lat1.cos() * lat2.cos();
 
-        let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    let c = two * a.sqrt().atan2((T::one() - a).sqrt());
 
-        EARTH_RADIUS * c
+    radius * c
+}
+
+// Canonical city graph computed once, so that all libraries ingest exactly the
+// same vertices and edges. Vertex indices are positions in `cities`.
+#[derive(Debug, Clone)]
+pub struct CityGraph<D = f32> {
+    pub cities: Vec<City>,
+    // Pairs `(i, j, distance)` with `i < j` of connected cities.
+    pub edges: Vec<(usize, usize, D)>,
+}
+
+impl<D: Distance> CityGraph<D> {
+    pub fn new(cities: Vec<City>) -> Self {
+        let edges = spatial::SpatialIndex::new(&cities).pairs();
+        Self::weighted(cities, &edges)
     }
 
-    pub fn are_connected(&self, other: &Self) -> Option<f32> {
-        let d = self.dist(other);
-        (d <= Self::MAX_DISTANCE && d > 0.0).then_some(d)
+    // The same graph with distances in another precision.
+    pub fn with_distance<E: Distance>(&self) -> CityGraph<E> {
+        CityGraph::weighted(self.cities.clone(), &self.edges)
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.cities.iter().position(|city| city.name == name)
+    }
+
+    fn weighted<W>(cities: Vec<City>, pairs: &[(usize, usize, W)]) -> Self {
+        let edges = pairs
+            .iter()
+            .map(|&(i, j, _)| (i, j, D::between(&cities[i], &cities[j])))
+            .collect();
+        Self { cities, edges }
+    }
+}
+
+// Shortest path between two cities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CityPath<D> {
+    pub distance: D,
+    // Ids of the cities from the start to the target, empty if the target is
+    // unreachable.
+    pub cities: Vec<u64>,
+}
+
+impl<D: Distance> CityPath<D> {
+    pub fn unreachable() -> Self {
+        Self {
+            distance: D::INFINITY,
+            cities: Vec::new(),
+        }
     }
 }
 
@@ -56,114 +152,1044 @@ struct CityRaw {
     coords: String,
 }
 
-pub fn load_cities() -> Vec<City> {
+// Problem with the cities dataset. Row numbers are line numbers in the CSV
+// file, including the header.
+#[derive(Debug)]
+pub enum CityLoadError {
+    Io(std::io::Error),
+    Csv(csv::Error),
+    MissingCoordinate { row: u64, coords: String },
+    InvalidLatitude { row: u64, value: String },
+    InvalidLongitude { row: u64, value: String },
+}
+
+impl CityLoadError {
+    // Whether the error concerns only a single row and the rest of the dataset
+    // can still be read.
+    pub fn is_row_error(&self) -> bool {
+        match self {
+            CityLoadError::Io(_) => false,
+            CityLoadError::Csv(error) => !matches!(error.kind(), csv::ErrorKind::Io(_)),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for CityLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CityLoadError::Io(error) => write!(f, "cannot read cities: {error}"),
+            CityLoadError::Csv(error) => write!(f, "invalid cities csv: {error}"),
+            CityLoadError::MissingCoordinate { row, coords } => {
+                write!(f, "row {row}: missing coordinate in \"{coords}\"")
+            }
+            CityLoadError::InvalidLatitude { row, value } => {
+                write!(f, "row {row}: invalid latitude \"{value}\"")
+            }
+            CityLoadError::InvalidLongitude { row, value } => {
+                write!(f, "row {row}: invalid longitude \"{value}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CityLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CityLoadError::Io(error) => Some(error),
+            CityLoadError::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CityLoadError {
+    fn from(error: std::io::Error) -> Self {
+        CityLoadError::Io(error)
+    }
+}
+
+impl From<csv::Error> for CityLoadError {
+    fn from(error: csv::Error) -> Self {
+        CityLoadError::Csv(error)
+    }
+}
+
+impl CityRaw {
+    fn into_city(self, row: u64) -> Result<City, CityLoadError> {
+        let mut coords = self.coords.split(',').map(str::trim);
+        let (Some(lat), Some(lon)) = (coords.next(), coords.next()) else {
+            return Err(CityLoadError::MissingCoordinate {
+                row,
+                coords: self.coords,
+            });
+        };
+
+        let lat = lat
+            .parse()
+            .ok()
+            .filter(|lat: &f64| (-90.0..=90.0).contains(lat))
+            .ok_or_else(|| CityLoadError::InvalidLatitude {
+                row,
+                value: lat.to_string(),
+            })?;
+        let lon = lon
+            .parse()
+            .ok()
+            .filter(|lon: &f64| (-180.0..=180.0).contains(lon))
+            .ok_or_else(|| CityLoadError::InvalidLongitude {
+                row,
+                value: lon.to_string(),
+            })?;
+
+        Ok(City {
+            id: self.id,
+            name: self.name,
+            pop: self.pop,
+            lat,
+            lon,
+        })
+    }
+}
+
+// Cities read from the dataset together with the rows that were skipped.
+#[derive(Debug)]
+pub struct LoadedCities {
+    pub cities: Vec<City>,
+    pub warnings: Vec<CityLoadError>,
+}
+
+// Environment variable overriding the location of the cities dataset.
+pub const CITIES_PATH_VAR: &str = "RUSTY_GRAPHS_CITIES";
+
+// Location of the cities dataset, either given by `RUSTY_GRAPHS_CITIES` or
+// `data/cities.csv` in the crate root, regardless of the working directory.
+pub fn cities_path() -> PathBuf {
+    std::env::var_os(CITIES_PATH_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("data/cities.csv"))
+}
+
+// Fails on the first problem with the dataset.
+pub fn load_cities() -> Result<Vec<City>, CityLoadError> {
+    load_cities_from(cities_path(), false).map(|loaded| loaded.cities)
+}
+
+// Skips rows that cannot be parsed and collects them as warnings. Fails only if
+// the dataset cannot be read at all.
+pub fn load_cities_lenient() -> Result<LoadedCities, CityLoadError> {
+    load_cities_from(cities_path(), true)
+}
+
+pub fn load_cities_from(
+    path: impl AsRef<Path>,
+    skip_invalid: bool,
+) -> Result<LoadedCities, CityLoadError> {
+    read_cities(std::fs::File::open(path)?, skip_invalid)
+}
+
+// Reads cities in the format of the geonames export from opendatasoft, that is,
+// semicolon-separated with a header. Gzip-compressed input is detected and
+// decompressed.
+pub fn read_cities(reader: impl Read, skip_invalid: bool) -> Result<LoadedCities, CityLoadError> {
+    let mut reader = BufReader::new(reader);
+
+    if reader.fill_buf()?.starts_with(&[0x1f, 0x8b]) {
+        parse_cities(MultiGzDecoder::new(reader), skip_invalid)
+    } else {
+        parse_cities(reader, skip_invalid)
+    }
+}
+
+fn parse_cities(reader: impl Read, skip_invalid: bool) -> Result<LoadedCities, CityLoadError> {
     let mut rdr = csv::ReaderBuilder::new()
         .delimiter(b';')
-        .from_reader(std::fs::File::open("data/cities.csv").unwrap());
-    rdr.deserialize()
-        .map(|result| result.unwrap())
-        .map(|record: CityRaw| {
-            let mut coords = record.coords.split(',');
-            let lat = coords.next().unwrap().parse().unwrap();
-            let lon = coords.next().unwrap().parse().unwrap();
-
-            City {
-                id: record.id,
-                name: record.name,
-                pop: record.pop,
-                lat,
-                lon,
+        .from_reader(reader);
+
+    let headers = rdr.headers()?.clone();
+    let mut record = csv::StringRecord::new();
+    let mut cities = Vec::new();
+    let mut warnings = Vec::new();
+
+    loop {
+        let city = match rdr.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                let row = record.position().map_or(0, |pos| pos.line());
+                record
+                    .deserialize::<CityRaw>(Some(&headers))
+                    .map_err(CityLoadError::from)
+                    .and_then(|raw| raw.into_city(row))
             }
-        })
-        .filter(|city| city.pop > 25_000)
-        .collect()
+            Err(error) => Err(CityLoadError::from(error)),
+        };
+
+        match city {
+            Ok(city) if city.pop > 25_000 => cities.push(city),
+            Ok(_) => {}
+            Err(error) if skip_invalid && error.is_row_error() => warnings.push(error),
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(LoadedCities { cities, warnings })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DependencyKind {
+    Normal,
+    Build,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dependency {
+    // A package can depend on another one in more ways at once (e.g., normal
+    // and build).
+    pub kinds: BTreeSet<DependencyKind>,
+    // Declared as optional, that is, activated by a feature.
+    pub optional: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct Package {
+    // Unique among loaded packages. It is `name@version`, followed by the
+    // source in parentheses if the same version comes from multiple sources.
+    pub id: String,
     pub name: String,
-    pub deps: BTreeSet<String>,
+    pub version: String,
+    // `None` for local packages.
+    pub source: Option<String>,
+    // Dependencies by their ids.
+    pub deps: BTreeMap<String, Dependency>,
 }
 
 impl Package {
-    fn new(name: &str) -> Self {
+    fn new(name: &str, version: &str, source: Option<&str>) -> Self {
         Self {
+            id: format!("{name}@{version}"),
             name: name.to_string(),
-            deps: BTreeSet::new(),
+            version: version.to_string(),
+            source: source.map(String::from),
+            deps: BTreeMap::new(),
         }
     }
 
-    fn add_dep(&mut self, other: String) {
-        if self.name != other {
-            self.deps.insert(other);
+    fn add_dep(&mut self, other: String, kind: DependencyKind, optional: bool) {
+        if self.id != other {
+            let dep = self.deps.entry(other).or_insert_with(|| Dependency {
+                kinds: BTreeSet::new(),
+                optional,
+            });
+            dep.kinds.insert(kind);
+            dep.optional &= optional;
         }
     }
 
     pub fn has_dependency(&self, other: &Self) -> bool {
-        self.deps.contains(other.name.as_str())
+        self.deps.contains_key(other.id.as_str())
     }
 }
 
-pub fn load_tree() -> Vec<Package> {
-    let output = std::process::Command::new("cargo")
-        .arg("tree")
-        .arg("--no-dedupe")
-        .args(["--prefix", "depth"])
-        .output()
-        .unwrap()
-        .stdout;
-    let output = std::str::from_utf8(&output).unwrap();
+// Environment variable overriding the source of the dependency tree, see
+// `load_tree_from`.
+pub const TREE_PATH_VAR: &str = "RUSTY_GRAPHS_TREE";
 
-    fn parse_line(line: &str) -> (usize, &str) {
-        let split = line
-            .char_indices()
-            .find(|(_, c)| !c.is_ascii_digit())
-            .unwrap()
-            .0;
+// Source of the dependency tree, either given by `RUSTY_GRAPHS_TREE` or this
+// crate.
+pub fn tree_path() -> PathBuf {
+    std::env::var_os(TREE_PATH_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+}
+
+pub fn load_tree() -> Result<Vec<Package>, TreeLoadError> {
+    load_tree_from(tree_path())
+}
 
-        let level = line[..split].parse().unwrap();
-        let name = line[split..].split_whitespace().next().unwrap();
+// Length of the cycle injected by `load_cyclic_tree`.
+pub const CYCLE_LENGTH: usize = 3;
+
+// The dependency tree with a cycle injected, see `inject_cycle`.
+pub fn load_cyclic_tree() -> Result<Vec<Package>, TreeLoadError> {
+    let mut packages = load_tree()?;
+    inject_cycle(&mut packages, CYCLE_LENGTH).ok_or(TreeLoadError::NoChain(CYCLE_LENGTH))?;
+    Ok(packages)
+}
 
-        (level, name)
+// Closes a shortest chain of dependencies `p0 -> p1 -> ... -> pk` (each
+// depending on the next) into a cycle by making the last package depend on the
+// first one. Being closed from a shortest chain, the cycle is the shortest one
+// going through the new dependency. Returns the cycle with each package being a
+// dependency of the next one, or `None` if there is no such chain of given
+// length.
+pub fn inject_cycle(packages: &mut [Package], length: usize) -> Option<Vec<String>> {
+    if length < 2 {
+        return None;
     }
 
-    let mut lines = output.split('\n');
-    let root_line = lines.next().unwrap();
+    let index = packages
+        .iter()
+        .enumerate()
+        .map(|(i, package)| (package.id.as_str(), i))
+        .collect::<HashMap<_, _>>();
 
-    let (mut last_level, name) = parse_line(root_line);
-    assert_eq!(last_level, 0);
+    let deps = |i: usize| {
+        packages[i]
+            .deps
+            .keys()
+            .filter_map(|dep| index.get(dep.as_str()).copied())
+    };
 
-    let mut stack = vec![Package::new(name)];
-    let mut packages = BTreeMap::new();
+    let mut chain = None;
 
-    for line in lines {
-        if line.is_empty() {
-            continue;
+    for first in 0..packages.len() {
+        // Breadth-first search gives the shortest chains from the first package.
+        let mut parent = HashMap::from([(first, first)]);
+        let mut level = vec![first];
+
+        for _ in 1..length {
+            let mut next = Vec::new();
+
+            for &i in level.iter() {
+                for j in deps(i) {
+                    if let Entry::Vacant(entry) = parent.entry(j) {
+                        entry.insert(i);
+                        next.push(j);
+                    }
+                }
+            }
+
+            level = next;
+        }
+
+        if let Some(&last) = level.first() {
+            let mut path = vec![last];
+            while *path.last().unwrap() != first {
+                path.push(parent[path.last().unwrap()]);
+            }
+
+            chain = Some(path);
+            break;
         }
+    }
 
-        let (level, name) = parse_line(line);
+    // Each package in the path is a dependency of the next one.
+    let path = chain?;
+    let (first, last) = (path[path.len() - 1], path[0]);
 
-        if level <= last_level {
-            while stack.len() > level {
-                let p = stack.pop().unwrap();
-                let q = packages
-                    .entry(p.name.clone())
-                    .or_insert_with(|| Package::new(&p.name));
+    let first = packages[first].id.clone();
+    packages[last].add_dep(first, DependencyKind::Normal, false);
 
-                for other in p.deps {
-                    q.add_dep(other);
-                }
+    Some(path.into_iter().map(|i| packages[i].id.clone()).collect())
+}
+
+// Loads the dependency tree from a Cargo project or workspace (its directory
+// or `Cargo.toml`), a lockfile (`*.lock`) or saved `cargo metadata` output
+// (any other file).
+pub fn load_tree_from(path: impl AsRef<Path>) -> Result<Vec<Package>, TreeLoadError> {
+    let path = path.as_ref();
+
+    if path.is_dir() {
+        load_tree_from_project(path.join("Cargo.toml"))
+    } else if path.file_name().is_some_and(|name| name == "Cargo.toml") {
+        load_tree_from_project(path)
+    } else if path.extension().is_some_and(|ext| ext == "lock") {
+        load_tree_from_lock(path)
+    } else {
+        load_tree_from_metadata(path)
+    }
+}
+
+// Dependency tree of all workspace members for the host platform, as resolved
+// by `cargo metadata`.
+pub fn load_tree_from_project(manifest: impl AsRef<Path>) -> Result<Vec<Package>, TreeLoadError> {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = run(std::process::Command::new(rustc).arg("-vV"))?;
+    let host = String::from_utf8_lossy(&output)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .ok_or_else(|| TreeLoadError::Command("cannot determine host platform".to_string()))?
+        .to_string();
+
+    let output = run(std::process::Command::new("cargo")
+        .arg("metadata")
+        .args(["--format-version", "1"])
+        .args(["--filter-platform", &host])
+        .arg("--manifest-path")
+        .arg(manifest.as_ref()))?;
+
+    read_tree_from_metadata(output.as_slice())
+}
+
+fn run(command: &mut std::process::Command) -> Result<Vec<u8>, TreeLoadError> {
+    let output = command.output()?;
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(TreeLoadError::Command(stderr.trim().to_string()))
+    }
+}
+
+// Problem with reading a dependency tree from a project, `Cargo.lock` or
+// `cargo metadata` output.
+#[derive(Debug)]
+pub enum TreeLoadError {
+    Io(std::io::Error),
+    Lock(toml::de::Error),
+    Metadata(serde_json::Error),
+    // The metadata were produced with `--no-deps`.
+    MissingResolve,
+    UnknownPackage(String),
+    // No chain of dependencies of given length to inject a cycle into.
+    NoChain(usize),
+    // Running cargo failed, with its error output.
+    Command(String),
+}
+
+impl fmt::Display for TreeLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeLoadError::Io(error) => write!(f, "cannot read dependency tree: {error}"),
+            TreeLoadError::Lock(error) => write!(f, "invalid lockfile: {error}"),
+            TreeLoadError::Metadata(error) => write!(f, "invalid cargo metadata: {error}"),
+            TreeLoadError::MissingResolve => {
+                write!(f, "cargo metadata do not contain resolved dependencies")
+            }
+            TreeLoadError::UnknownPackage(id) => write!(f, "unknown package {id}"),
+            TreeLoadError::NoChain(length) => {
+                write!(f, "dependency tree has no chain of {length} packages")
             }
+            TreeLoadError::Command(stderr) => write!(f, "running cargo failed: {stderr}"),
         }
+    }
+}
+
+impl std::error::Error for TreeLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TreeLoadError::Io(error) => Some(error),
+            TreeLoadError::Lock(error) => Some(error),
+            TreeLoadError::Metadata(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TreeLoadError {
+    fn from(error: std::io::Error) -> Self {
+        TreeLoadError::Io(error)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LockPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    // Entries are "name", "name version" or "name version (source)", depending
+    // on what is needed to identify the package.
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    #[serde(default)]
+    workspace_members: Vec<String>,
+    resolve: Option<MetadataResolve>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    version: String,
+    source: Option<String>,
+    dependencies: Vec<MetadataDependency>,
+}
+
+// Dependency as declared in the manifest.
+#[derive(Debug, Deserialize)]
+struct MetadataDependency {
+    name: String,
+    kind: Option<String>,
+    optional: bool,
+}
 
-        stack.last_mut().unwrap().add_dep(name.to_string());
-        stack.push(Package::new(name));
+#[derive(Debug, Deserialize)]
+struct MetadataResolve {
+    nodes: Vec<MetadataNode>,
+    root: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataNode {
+    id: String,
+    deps: Vec<MetadataNodeDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataNodeDep {
+    pkg: String,
+    dep_kinds: Vec<MetadataDepKind>,
+}
 
-        last_level = level;
+#[derive(Debug, Deserialize)]
+struct MetadataDepKind {
+    kind: Option<String>,
+}
+
+// `None` for dev-dependencies, which are not part of the build graph. A crate
+// can dev-depend on a crate depending on it, which is not a cycle.
+fn dependency_kind(kind: Option<&str>) -> Option<DependencyKind> {
+    match kind {
+        Some("build") => Some(DependencyKind::Build),
+        Some("dev") => None,
+        _ => Some(DependencyKind::Normal),
+    }
+}
+
+// Packages identified by arbitrary keys and dependencies `(package, dependency,
+// kind, optional)` between them.
+fn collect_packages<K: Ord + Copy>(
+    mut packages: BTreeMap<K, Package>,
+    deps: impl IntoIterator<Item = (K, K, DependencyKind, bool)>,
+) -> Vec<Package> {
+    let mut versions = BTreeMap::new();
+    for package in packages.values() {
+        *versions.entry(package.id.clone()).or_insert(0) += 1;
+    }
+
+    for package in packages.values_mut() {
+        if versions[&package.id] > 1 {
+            let source = package.source.as_deref().unwrap_or("local");
+            package.id = format!("{} ({source})", package.id);
+        }
     }
 
-    let p = stack.remove(0);
-    packages.insert(p.name.clone(), p);
+    let ids = packages
+        .iter()
+        .map(|(&key, package)| (key, package.id.clone()))
+        .collect::<BTreeMap<_, _>>();
+
+    for (package, dep, kind, optional) in deps {
+        if let (Some(package), Some(dep)) = (packages.get_mut(&package), ids.get(&dep)) {
+            package.add_dep(dep.clone(), kind, optional);
+        }
+    }
 
     packages.into_values().collect()
 }
+
+// All packages in the lockfile, which includes dependencies for all platforms.
+// Lockfiles do not record dependency kinds, so all dependencies are normal.
+pub fn load_tree_from_lock(path: impl AsRef<Path>) -> Result<Vec<Package>, TreeLoadError> {
+    read_tree_from_lock(std::fs::File::open(path)?)
+}
+
+pub fn read_tree_from_lock(mut reader: impl Read) -> Result<Vec<Package>, TreeLoadError> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let lockfile: Lockfile = toml::from_str(&content).map_err(TreeLoadError::Lock)?;
+
+    // Packages by name, usually only one version each.
+    let mut by_name = HashMap::<&str, Vec<usize>>::new();
+    for (i, p) in lockfile.package.iter().enumerate() {
+        by_name.entry(&p.name).or_default().push(i);
+    }
+
+    let find = |dep: &str| {
+        let mut parts = dep.split_whitespace();
+        let name = parts.next().unwrap_or_default();
+        let version = parts.next();
+        let source = parts
+            .next()
+            .map(|source| source.trim_start_matches('(').trim_end_matches(')'));
+
+        by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .find(|&i| {
+                let p = &lockfile.package[i];
+                version.is_none_or(|version| p.version == version)
+                    && source.is_none_or(|source| p.source.as_deref() == Some(source))
+            })
+            .ok_or_else(|| TreeLoadError::UnknownPackage(dep.to_string()))
+    };
+
+    let mut deps = Vec::new();
+    for (i, p) in lockfile.package.iter().enumerate() {
+        for dep in p.dependencies.iter() {
+            deps.push((i, find(dep)?, DependencyKind::Normal, false));
+        }
+    }
+
+    let packages = lockfile
+        .package
+        .iter()
+        .map(|p| Package::new(&p.name, &p.version, p.source.as_deref()))
+        .enumerate()
+        .collect();
+
+    Ok(collect_packages(packages, deps))
+}
+
+// Packages reachable from the workspace members by normal and build
+// dependencies in the output of `cargo metadata --format-version 1`.
+pub fn load_tree_from_metadata(path: impl AsRef<Path>) -> Result<Vec<Package>, TreeLoadError> {
+    read_tree_from_metadata(std::fs::File::open(path)?)
+}
+
+pub fn read_tree_from_metadata(reader: impl Read) -> Result<Vec<Package>, TreeLoadError> {
+    let metadata: Metadata =
+        serde_json::from_reader(BufReader::new(reader)).map_err(TreeLoadError::Metadata)?;
+    let resolve = metadata.resolve.ok_or(TreeLoadError::MissingResolve)?;
+
+    let packages = metadata
+        .packages
+        .iter()
+        .map(|p| (p.id.as_str(), p))
+        .collect::<BTreeMap<_, _>>();
+    let package = |id: &str| {
+        packages
+            .get(id)
+            .copied()
+            .ok_or_else(|| TreeLoadError::UnknownPackage(id.to_string()))
+    };
+
+    let nodes = resolve
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut reachable = if !metadata.workspace_members.is_empty() {
+        metadata
+            .workspace_members
+            .iter()
+            .map(String::as_str)
+            .collect()
+    } else if let Some(root) = &resolve.root {
+        vec![root.as_str()]
+    } else {
+        nodes.keys().copied().collect()
+    };
+    let mut visited = BTreeMap::new();
+    let mut deps = Vec::new();
+
+    while let Some(id) = reachable.pop() {
+        if visited.contains_key(id) {
+            continue;
+        }
+
+        let p = package(id)?;
+        visited.insert(id, Package::new(&p.name, &p.version, p.source.as_deref()));
+
+        let node = nodes
+            .get(id)
+            .ok_or_else(|| TreeLoadError::UnknownPackage(id.to_string()))?;
+
+        for dep in node.deps.iter() {
+            let name = &package(&dep.pkg)?.name;
+
+            let kinds = dep
+                .dep_kinds
+                .iter()
+                .filter_map(|dep_kind| dependency_kind(dep_kind.kind.as_deref()))
+                .collect::<BTreeSet<_>>();
+
+            for &kind in kinds.iter() {
+                // The dependency can be declared multiple times for different
+                // targets, it is optional only if all of them are.
+                let mut declared = p
+                    .dependencies
+                    .iter()
+                    .filter(|d| &d.name == name && dependency_kind(d.kind.as_deref()) == Some(kind))
+                    .peekable();
+                let optional = declared.peek().is_some() && declared.all(|d| d.optional);
+
+                deps.push((id, dep.pkg.as_str(), kind, optional));
+            }
+
+            if !kinds.is_empty() {
+                reachable.push(&dep.pkg);
+            }
+        }
+    }
+
+    Ok(collect_packages(visited, deps))
+}
+
+// Common interface over the compared libraries, so that a single driver can run
+// all exercises against all of them. Each method corresponds to one measured
+// phase of an exercise. Algorithms return `None` if the library does not
+// provide them.
+pub trait GraphLibrary {
+    const NAME: &'static str;
+    // Algorithm phases that the library provides.
+    const ALGORITHMS: &'static [Phase];
+    // Edge weight types that the library supports without converting them to
+    // another type.
+    const WEIGHTS: &'static [Weight] = &Weight::ALL;
+    // Whether the vertices are added to the graph on their own. Libraries that
+    // build the whole graph from its edges do so when connecting the vertices,
+    // and adding the vertices is then not applicable.
+    const ADDS_VERTICES: bool = true;
+
+    type Cities<'a, D: Distance>;
+    type CityId: Copy;
+    type Packages<'a>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D>;
+    fn connect_cities<'a, D: Distance>(
+        graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D>;
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize);
+    // Vertices of the start and target cities, or `None` if either of them is
+    // missing.
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(Self::CityId, Self::CityId)>;
+    // Vertex of the city at given index of the input graph.
+    fn city_id<D: Distance>(graph: &Self::Cities<'_, D>, index: usize) -> Self::CityId;
+
+    fn dijkstra<D: Distance>(
+        _graph: &Self::Cities<'_, D>,
+        _start: Self::CityId,
+        _target: Self::CityId,
+    ) -> Option<D> {
+        None
+    }
+
+    fn dijkstra_with_goal<D: Distance>(
+        _graph: &Self::Cities<'_, D>,
+        _start: Self::CityId,
+        _target: Self::CityId,
+    ) -> Option<D> {
+        None
+    }
+
+    // Shortest path from the start to the target, searched with the goal if the
+    // library allows it.
+    fn shortest_path<D: Distance>(
+        _graph: &Self::Cities<'_, D>,
+        _start: Self::CityId,
+        _target: Self::CityId,
+    ) -> Option<CityPath<D>> {
+        None
+    }
+
+    // Distance from the start to the target found by A* with
+    // `Distance::estimate` to the target as the heuristic, and the number of
+    // distinct vertices taken from the queue.
+    fn astar<D: Distance>(
+        _graph: &Self::Cities<'_, D>,
+        _start: Self::CityId,
+        _target: Self::CityId,
+    ) -> Option<(D, usize)> {
+        None
+    }
+
+    // Distances from the start to all cities, in the order of the input graph.
+    // Unreachable cities have infinite distance.
+    fn dijkstra_distances<D: Distance>(
+        _graph: &Self::Cities<'_, D>,
+        _start: Self::CityId,
+    ) -> Option<Vec<D>> {
+        None
+    }
+
+    // Total weight of the minimum spanning forest and the number of its trees,
+    // that is, of the connected components.
+    fn spanning_forest<D: Distance>(_graph: &Self::Cities<'_, D>) -> Option<(D, usize)> {
+        None
+    }
+
+    // Component of each city, in the order of the input graph. The labels are
+    // arbitrary, only the cities in the same component have the same label.
+    fn connected_components<D: Distance>(_graph: &Self::Cities<'_, D>) -> Option<Vec<usize>> {
+        None
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_>;
+    fn connect_packages(graph: Self::Packages<'_>) -> Self::Packages<'_>;
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize);
+
+    fn toposort<'g>(_graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        None
+    }
+
+    // Packages grouped into levels, each depending only on the earlier ones,
+    // with every package in the earliest possible level.
+    fn topological_levels<'g>(_graph: &'g Self::Packages<'_>) -> Option<Vec<Vec<&'g str>>> {
+        None
+    }
+
+    // Cycle in the dependencies as reported by the library when the topological
+    // sort fails, each package being a dependency of the next one.
+    fn find_cycle<'g>(_graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        None
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    // Packages `name@1.0.0` with dependencies given by names.
+    pub(crate) fn packages(deps: &[(&str, &[&str])]) -> Vec<Package> {
+        deps.iter()
+            .map(|&(name, deps)| {
+                let mut package = Package::new(name, "1.0.0", None);
+                for dep in deps {
+                    package.add_dep(format!("{dep}@1.0.0"), DependencyKind::Normal, false);
+                }
+                package
+            })
+            .collect()
+    }
+
+    pub(crate) fn city(id: u64, lat: f64, lon: f64) -> City {
+        City {
+            id,
+            name: format!("city {id}"),
+            pop: 100_000,
+            lat,
+            lon,
+        }
+    }
+
+    #[test]
+    fn city_graph_connects_close_cities() {
+        // About 222 km apart along the meridian, so that only the neighbors
+        // are connected.
+        let cities = vec![
+            city(1, 0.0, 0.0),
+            city(2, 4.0, 0.0),
+            city(3, 2.0, 0.0),
+            city(4, 2.0, 0.0),
+        ];
+        let graph = CityGraph::<Meters>::new(cities);
+
+        let pairs = graph
+            .edges
+            .iter()
+            .map(|&(i, j, _)| (i, j))
+            .collect::<Vec<_>>();
+        // The cities at the same place are not connected.
+        assert_eq!(pairs, [(0, 2), (0, 3), (1, 2), (1, 3)]);
+
+        for &(i, j, d) in graph.edges.iter() {
+            assert_eq!(d, Meters::between(&graph.cities[i], &graph.cities[j]));
+        }
+
+        let graph = graph.with_distance::<f64>();
+        assert_eq!(graph.edges.len(), 4);
+        assert!((graph.edges[0].2 - 222.39).abs() < 0.01);
+
+        assert_eq!(graph.position("city 3"), Some(2));
+        assert_eq!(graph.position("city 5"), None);
+    }
+
+    const CITIES_CSV: &str = "\
+Geoname ID;Name;Population;Coordinates
+1;Big;30000;10.5, -20.25
+2;Small;1000;0, 0
+3;Broken;50000;95, 0
+4;Lonely;50000;12
+";
+
+    #[test]
+    fn read_cities_csv() {
+        let loaded = read_cities(CITIES_CSV.as_bytes(), true).unwrap();
+
+        assert_eq!(
+            loaded.cities,
+            [City {
+                id: 1,
+                name: String::from("Big"),
+                pop: 30000,
+                lat: 10.5,
+                lon: -20.25,
+            }]
+        );
+        assert!(matches!(
+            loaded.warnings[..],
+            [
+                CityLoadError::InvalidLatitude { row: 4, .. },
+                CityLoadError::MissingCoordinate { row: 5, .. },
+            ]
+        ));
+
+        assert!(matches!(
+            read_cities(CITIES_CSV.as_bytes(), false),
+            Err(CityLoadError::InvalidLatitude { row: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn read_cities_gzip() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(CITIES_CSV.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let loaded = read_cities(compressed.as_slice(), true).unwrap();
+        assert_eq!(loaded.cities.len(), 1);
+        assert_eq!(loaded.warnings.len(), 2);
+    }
+
+    const LOCKFILE: &str = r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["lib", "dup 1.0.0"]
+
+[[package]]
+name = "lib"
+version = "0.2.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["dup 2.0.0 (registry+https://github.com/rust-lang/crates.io-index)"]
+
+[[package]]
+name = "dup"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "dup"
+version = "2.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "dup"
+version = "2.0.0"
+source = "git+https://example.com/dup"
+"#;
+
+    #[test]
+    fn read_lockfile() {
+        let packages = read_tree_from_lock(LOCKFILE.as_bytes()).unwrap();
+
+        let ids = packages
+            .iter()
+            .map(|package| package.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                "app@0.1.0",
+                "lib@0.2.0",
+                "dup@1.0.0",
+                "dup@2.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                "dup@2.0.0 (git+https://example.com/dup)",
+            ]
+        );
+
+        let deps = |i: usize| packages[i].deps.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(deps(0), ["dup@1.0.0", "lib@0.2.0"]);
+        assert_eq!(deps(1), [ids[3]]);
+        assert!(deps(2).is_empty());
+        assert!(packages[0]
+            .deps
+            .values()
+            .all(|dep| !dep.optional && dep.kinds == BTreeSet::from([DependencyKind::Normal])));
+    }
+
+    #[test]
+    fn read_lockfile_unknown_dependency() {
+        let lockfile = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["dup 3.0.0"]
+"#;
+
+        assert!(matches!(
+            read_tree_from_lock(lockfile.as_bytes()),
+            Err(TreeLoadError::UnknownPackage(dep)) if dep == "dup 3.0.0"
+        ));
+    }
+
+    #[test]
+    fn read_metadata_without_dev_dependencies() {
+        // The macros crate dev-depends on the app, which depends on it.
+        let metadata = r#"{
+            "packages": [
+                {"id": "app", "name": "app", "version": "0.1.0", "source": null, "dependencies": [
+                    {"name": "macros", "kind": null, "optional": false}
+                ]},
+                {"id": "macros", "name": "macros", "version": "0.1.0", "source": null, "dependencies": [
+                    {"name": "app", "kind": "dev", "optional": false},
+                    {"name": "test-util", "kind": "dev", "optional": false}
+                ]},
+                {"id": "test-util", "name": "test-util", "version": "0.1.0", "source": null, "dependencies": []}
+            ],
+            "workspace_members": ["app", "macros"],
+            "resolve": {"root": null, "nodes": [
+                {"id": "app", "deps": [{"pkg": "macros", "dep_kinds": [{"kind": null}]}]},
+                {"id": "macros", "deps": [
+                    {"pkg": "app", "dep_kinds": [{"kind": "dev"}]},
+                    {"pkg": "test-util", "dep_kinds": [{"kind": "dev"}]}
+                ]},
+                {"id": "test-util", "deps": []}
+            ]}
+        }"#;
+
+        let packages = read_tree_from_metadata(metadata.as_bytes()).unwrap();
+
+        let ids = packages
+            .iter()
+            .map(|package| package.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["app@0.1.0", "macros@0.1.0"]);
+        assert!(packages[0].has_dependency(&packages[1]));
+        assert!(packages[1].deps.is_empty());
+        assert_eq!(reference::find_cycle(&packages), None);
+    }
+
+    #[test]
+    fn inject_cycle_closes_shortest_chain() {
+        // The last package of the chain does not need other dependencies.
+        let mut chain = packages(&[("a", &["b"]), ("b", &["c"]), ("c", &[])]);
+        let cycle = inject_cycle(&mut chain, 3).unwrap();
+        assert_eq!(cycle, ["c@1.0.0", "b@1.0.0", "a@1.0.0"]);
+        assert!(chain[2].has_dependency(&chain[0]));
+
+        let mut packages = packages(&[
+            ("a", &["b", "d"]),
+            ("b", &["c"]),
+            ("c", &["e"]),
+            ("d", &["c"]),
+            ("e", &[]),
+        ]);
+
+        assert_eq!(inject_cycle(&mut packages.clone(), 5), None);
+        assert_eq!(inject_cycle(&mut packages.clone(), 1), None);
+
+        let cycle = inject_cycle(&mut packages, 3).unwrap();
+        assert_eq!(cycle, ["c@1.0.0", "b@1.0.0", "a@1.0.0"]);
+        assert!(packages[2].has_dependency(&packages[0]));
+        assert!(packages[2].has_dependency(&packages[4]));
+
+        let cycle = cycle.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(reference::verify_cycle(&packages, &cycle), Ok(()));
+    }
+}
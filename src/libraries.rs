@@ -0,0 +1,28 @@
+mod graph;
+mod graphific;
+mod graphlib;
+mod gryf;
+mod pathfinding;
+mod petgraph;
+mod prepona;
+
+use crate::GraphLibrary;
+
+pub use self::{
+    graph::Graph, graphific::Graphific, graphlib::Graphlib, gryf::Gryf, pathfinding::Pathfinding,
+    petgraph::Petgraph, prepona::Prepona,
+};
+
+pub trait LibraryVisitor {
+    fn visit<L: GraphLibrary>(&mut self);
+}
+
+pub fn for_each<V: LibraryVisitor>(visitor: &mut V) {
+    visitor.visit::<Petgraph>();
+    visitor.visit::<Prepona>();
+    visitor.visit::<Pathfinding>();
+    visitor.visit::<Graph>();
+    visitor.visit::<Graphlib>();
+    visitor.visit::<Graphific>();
+    visitor.visit::<Gryf>();
+}
@@ -0,0 +1,147 @@
+use graph::prelude::{
+    wcc_afforest, Components as _, DirectedCsrGraph, Graph as _, GraphBuilder, NodeValues as _,
+    UndirectedCsrGraph, UndirectedNeighborsWithValues as _, WccConfig,
+};
+
+use crate::{exercise::Phase, City, CityGraph, Distance, GraphLibrary, Package};
+
+pub struct Graph;
+
+// The CSR representation is built from all vertices and edges at once, so
+// adding vertices only remembers the input and connecting builds the graph.
+pub struct GraphCities<'a, D> {
+    cities: &'a [City],
+    graph: Option<UndirectedCsrGraph<usize, &'a City, D>>,
+}
+
+pub struct GraphPackages<'a> {
+    packages: &'a [Package],
+    graph: Option<DirectedCsrGraph<usize, &'a Package>>,
+}
+
+impl GraphLibrary for Graph {
+    const NAME: &'static str = "graph";
+    const ALGORITHMS: &'static [Phase] = &[Phase::ConnectedComponents];
+    // The graph is built at once from the edges.
+    const ADDS_VERTICES: bool = false;
+
+    type Cities<'a, D: Distance> = GraphCities<'a, D>;
+    type CityId = usize;
+    type Packages<'a> = GraphPackages<'a>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        GraphCities {
+            cities: &input.cities,
+            graph: None,
+        }
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        let cities = graph.cities;
+
+        let graph = GraphBuilder::new()
+            .edges_with_values(input.edges.iter().copied())
+            .node_values(cities.iter())
+            .build();
+
+        GraphCities {
+            cities,
+            graph: Some(graph),
+        }
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        graph.graph.as_ref().map_or((graph.cities.len(), 0), |g| {
+            (g.node_count(), g.edge_count())
+        })
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(usize, usize)> {
+        let graph = graph.graph.as_ref().unwrap();
+
+        let (start, target) = (0..graph.node_count()).fold((None, None), |(s, t), v| {
+            let name = &graph.node_value(v).name;
+            (
+                s.or((*name == start).then_some(v)),
+                t.or((*name == target).then_some(v)),
+            )
+        });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(_graph: &Self::Cities<'_, D>, index: usize) -> usize {
+        index
+    }
+
+    fn connected_components<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<Vec<usize>> {
+        let graph = graph.graph.as_ref().unwrap();
+
+        // The algorithm requires a directed graph, so it is built from the
+        // edges in one direction, which is enough for weakly connected
+        // components.
+        let edges = (0..graph.node_count())
+            .flat_map(|u| {
+                graph
+                    .neighbors_with_values(u)
+                    .filter(move |v| u < v.target)
+                    .map(move |v| (u, v.target))
+            })
+            .collect::<Vec<_>>();
+
+        let directed: DirectedCsrGraph<usize, ()> = GraphBuilder::new()
+            .edges(edges)
+            .node_values(vec![(); graph.node_count()])
+            .build();
+
+        Some(wcc_afforest(&directed, WccConfig::default()).to_vec())
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        GraphPackages {
+            packages,
+            graph: None,
+        }
+    }
+
+    fn connect_packages(graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        let packages = graph.packages;
+
+        let mut edges = Vec::with_capacity(packages.len() * 5);
+        for i in 0..packages.len() {
+            let src = &packages[i];
+
+            #[allow(clippy::needless_range_loop)]
+            for j in 0..packages.len() {
+                let dst = &packages[j];
+
+                if dst.has_dependency(src) {
+                    edges.push((i, j));
+                }
+            }
+        }
+
+        let graph = GraphBuilder::new()
+            .edges(edges)
+            .node_values(packages.iter())
+            .build();
+
+        GraphPackages {
+            packages,
+            graph: Some(graph),
+        }
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        graph.graph.as_ref().map_or((graph.packages.len(), 0), |g| {
+            (g.node_count(), g.edge_count())
+        })
+    }
+}
@@ -0,0 +1,116 @@
+use graphific::{AnyGraph, BasicDirectedGraph, BasicUndirectedGraph, Vertex};
+
+use crate::{exercise::Phase, City, CityGraph, Distance, GraphLibrary, Package};
+
+pub struct Graphific;
+
+pub struct GraphificCities<'a> {
+    cities: &'a [City],
+    graph: BasicUndirectedGraph<usize, ()>,
+}
+
+pub struct GraphificPackages<'a> {
+    packages: &'a [Package],
+    graph: BasicDirectedGraph<usize, ()>,
+}
+
+impl GraphLibrary for Graphific {
+    const NAME: &'static str = "graphific";
+    const ALGORITHMS: &'static [Phase] = &[];
+
+    type Cities<'a, D: Distance> = GraphificCities<'a>;
+    type CityId = usize;
+    type Packages<'a> = GraphificPackages<'a>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        let mut graph = BasicUndirectedGraph::new();
+
+        for (key, _) in input.cities.iter().enumerate() {
+            let v = Vertex::with_value(key, ());
+            graph = graph.add_vertex(v).unwrap();
+        }
+
+        GraphificCities {
+            cities: &input.cities,
+            graph,
+        }
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        let GraphificCities { cities, mut graph } = graph;
+
+        for &(i, j, _) in input.edges.iter() {
+            graph = graph.add_edge_between_keys(i, j).unwrap();
+        }
+
+        GraphificCities { cities, graph }
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        (graph.graph.vertices().len(), graph.graph.edges().len())
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(usize, usize)> {
+        let (start, target) = graph
+            .graph
+            .vertices()
+            .into_iter()
+            .fold((None, None), |(s, t), v| {
+                let name = &graph.cities[*v.key()].name;
+                (
+                    s.or((*name == start).then_some(*v.key())),
+                    t.or((*name == target).then_some(*v.key())),
+                )
+            });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(_graph: &Self::Cities<'_, D>, index: usize) -> usize {
+        index
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        let mut graph = BasicDirectedGraph::new();
+
+        for (key, _) in packages.iter().enumerate() {
+            let v = Vertex::with_value(key, ());
+            graph = graph.add_vertex(v).unwrap();
+        }
+
+        GraphificPackages { packages, graph }
+    }
+
+    fn connect_packages(graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        let GraphificPackages {
+            packages,
+            mut graph,
+        } = graph;
+
+        for i in 0..packages.len() {
+            let src = &packages[i];
+
+            #[allow(clippy::needless_range_loop)]
+            for j in 0..packages.len() {
+                let dst = &packages[j];
+
+                if dst.has_dependency(src) {
+                    graph = graph.add_edge_between_keys(i, j).unwrap();
+                }
+            }
+        }
+
+        GraphificPackages { packages, graph }
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        (graph.graph.vertices().len(), graph.graph.edges().len())
+    }
+}
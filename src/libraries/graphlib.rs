@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use graphlib::{iterators::Dijkstra, Graph, VertexId};
+
+use crate::{exercise::Phase, City, CityGraph, CityPath, Distance, GraphLibrary, Package, Weight};
+
+pub struct Graphlib;
+
+pub struct GraphlibCities<'a> {
+    graph: Graph<&'a City>,
+    vertices: Vec<VertexId>,
+}
+
+pub struct GraphlibPackages<'a> {
+    graph: Graph<&'a Package>,
+    vertices: Vec<VertexId>,
+}
+
+impl GraphLibrary for Graphlib {
+    const NAME: &'static str = "graphlib";
+    const ALGORITHMS: &'static [Phase] = &[
+        Phase::Dijkstra,
+        Phase::ShortestPath,
+        Phase::TopologicalSort,
+        Phase::TopologicalLevels,
+    ];
+    const WEIGHTS: &'static [Weight] = &[Weight::F32];
+
+    type Cities<'a, D: Distance> = GraphlibCities<'a>;
+    type CityId = VertexId;
+    type Packages<'a> = GraphlibPackages<'a>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        let mut graph = Graph::new();
+        let mut vertices = Vec::with_capacity(input.cities.len());
+
+        for city in input.cities.iter() {
+            let v = graph.add_vertex(city);
+            vertices.push(v);
+        }
+
+        GraphlibCities { graph, vertices }
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        mut graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        let GraphlibCities { graph: g, vertices } = &mut graph;
+
+        for &(i, j, edge) in input.edges.iter() {
+            let i = &vertices[i];
+            let j = &vertices[j];
+
+            // Weights are required to be in range [0, 1].
+            // Only f32 weights are supported, so other types lose precision.
+            // Rounded weights can slightly exceed the maximum distance.
+            let edge_norm = (edge.to_f64() as f32 / City::MAX_DISTANCE).min(1.0);
+
+            g.add_edge_with_weight(i, j, edge_norm).unwrap();
+
+            // Graph is directed and that cannot be changed.
+            g.add_edge_with_weight(j, i, edge_norm).unwrap();
+        }
+
+        graph
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        (graph.graph.vertex_count(), graph.graph.edge_count())
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(VertexId, VertexId)> {
+        let graph = &graph.graph;
+
+        let (start, target) = graph.vertices().fold((None, None), |(s, t), v| {
+            let name = &graph.fetch(v).unwrap().name;
+            (
+                s.or((*name == start).then_some(*v)),
+                t.or((*name == target).then_some(*v)),
+            )
+        });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(graph: &Self::Cities<'_, D>, index: usize) -> VertexId {
+        graph.vertices[index]
+    }
+
+    fn dijkstra<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+        target: VertexId,
+    ) -> Option<D> {
+        let mut result = Dijkstra::new(&graph.graph, &start).unwrap();
+        let distance = result.get_distance(&target).unwrap();
+
+        // Unreachable vertices have the distance of `f32::MAX`.
+        if distance == f32::MAX {
+            Some(D::INFINITY)
+        } else {
+            Some(D::from_f64((distance * City::MAX_DISTANCE).into()))
+        }
+    }
+
+    fn shortest_path<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+        target: VertexId,
+    ) -> Option<CityPath<D>> {
+        let mut result = Dijkstra::new(&graph.graph, &start).unwrap();
+        let distance = result.get_distance(&target).unwrap();
+
+        if distance == f32::MAX {
+            return Some(CityPath::unreachable());
+        }
+
+        let cities = result
+            .get_path_to(&target)
+            .unwrap()
+            .map(|v| graph.graph.fetch(v).unwrap().id)
+            .collect();
+
+        Some(CityPath {
+            distance: D::from_f64((distance * City::MAX_DISTANCE).into()),
+            cities,
+        })
+    }
+
+    fn dijkstra_distances<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+    ) -> Option<Vec<D>> {
+        let mut result = Dijkstra::new(&graph.graph, &start).unwrap();
+        let distances = graph
+            .vertices
+            .iter()
+            .map(|v| match result.get_distance(v).unwrap() {
+                f32::MAX => D::INFINITY,
+                distance => D::from_f64((distance * City::MAX_DISTANCE).into()),
+            })
+            .collect();
+
+        Some(distances)
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        let mut graph = Graph::new();
+        let mut vertices = Vec::with_capacity(packages.len());
+
+        for package in packages.iter() {
+            let v = graph.add_vertex(package);
+            vertices.push(v);
+        }
+
+        GraphlibPackages { graph, vertices }
+    }
+
+    fn connect_packages(mut graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        let GraphlibPackages { graph: g, vertices } = &mut graph;
+
+        for i in 0..g.vertex_count() {
+            for j in 0..g.vertex_count() {
+                let i = &vertices[i];
+                let j = &vertices[j];
+
+                let src = g.fetch(i).unwrap();
+                let dst = g.fetch(j).unwrap();
+
+                if dst.has_dependency(src) {
+                    g.add_edge(i, j).unwrap();
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        (graph.graph.vertex_count(), graph.graph.edge_count())
+    }
+
+    fn toposort<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        let graph = &graph.graph;
+
+        let sorted = graph
+            .topo()
+            .map(|v| graph.fetch(v).unwrap().id.as_str())
+            .collect();
+
+        Some(sorted)
+    }
+
+    fn topological_levels<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<Vec<&'g str>>> {
+        let graph = &graph.graph;
+
+        let mut level = HashMap::with_capacity(graph.vertex_count());
+        let mut levels: Vec<Vec<&str>> = Vec::new();
+
+        for v in graph.topo() {
+            let l = level.get(v).copied().unwrap_or(0);
+
+            for w in graph.out_neighbors(v) {
+                let lw = level.entry(w).or_insert(0);
+                *lw = (*lw).max(l + 1);
+            }
+
+            if l == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[l].push(graph.fetch(v).unwrap().id.as_str());
+        }
+
+        Some(levels)
+    }
+}
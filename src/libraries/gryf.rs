@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use gryf::{
+    algo::{toposort, ShortestPaths, TopoSort},
+    core::{
+        base::{NeighborReference, VertexReference},
+        id::{IdType, VertexId},
+        marker::{Directed, Direction, Undirected},
+    },
+    visit::{Bfs, VisitSet, Visitor},
+    Graph,
+};
+
+use crate::{exercise::Phase, City, CityGraph, CityPath, Distance, GraphLibrary, Package};
+
+pub struct Gryf;
+
+impl GraphLibrary for Gryf {
+    const NAME: &'static str = "gryf";
+    const ALGORITHMS: &'static [Phase] = &[
+        Phase::Dijkstra,
+        Phase::DijkstraWithGoal,
+        Phase::ShortestPath,
+        Phase::ConnectedComponents,
+        Phase::TopologicalSort,
+        Phase::TopologicalLevels,
+        Phase::FindCycle,
+    ];
+
+    type Cities<'a, D: Distance> = Graph<&'a City, D, Undirected>;
+    type CityId = VertexId;
+    type Packages<'a> = Graph<&'a Package, (), Directed>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        let mut graph = Graph::new_undirected();
+
+        for city in input.cities.iter() {
+            graph.add_vertex(city);
+        }
+
+        graph
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        mut graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        for &(i, j, edge) in input.edges.iter() {
+            graph.add_edge(VertexId::from_usize(i), VertexId::from_usize(j), edge);
+        }
+
+        graph
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        (graph.vertex_count(), graph.edge_count())
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(VertexId, VertexId)> {
+        let (start, target) = graph.vertices().fold((None, None), |(s, t), v| {
+            let name = &v.attr().name;
+            (
+                s.or((*name == start).then_some(*v.id())),
+                t.or((*name == target).then_some(*v.id())),
+            )
+        });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(_graph: &Self::Cities<'_, D>, index: usize) -> VertexId {
+        VertexId::from_usize(index)
+    }
+
+    fn dijkstra<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+        target: VertexId,
+    ) -> Option<D> {
+        let result = ShortestPaths::on(graph).dijkstra().run(start).unwrap();
+        Some(result.dist(target).copied().unwrap_or(D::INFINITY))
+    }
+
+    fn dijkstra_with_goal<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+        target: VertexId,
+    ) -> Option<D> {
+        // Unreachable goal is reported as an error.
+        let dist = ShortestPaths::on(graph)
+            .goal(target)
+            .dijkstra()
+            .run(start)
+            .ok()
+            .and_then(|result| result.dist(target).copied());
+        Some(dist.unwrap_or(D::INFINITY))
+    }
+
+    fn shortest_path<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+        target: VertexId,
+    ) -> Option<CityPath<D>> {
+        let Ok(result) = ShortestPaths::on(graph).goal(target).dijkstra().run(start) else {
+            return Some(CityPath::unreachable());
+        };
+
+        // The reconstruction goes from the target to the start and does not
+        // include the target itself.
+        let mut path = result.reconstruct(target).collect::<Vec<_>>();
+        path.reverse();
+        path.push(target);
+
+        Some(CityPath {
+            distance: result[target],
+            cities: path
+                .into_iter()
+                .map(|v| graph.vertex(&v).unwrap().id)
+                .collect(),
+        })
+    }
+
+    fn dijkstra_distances<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: VertexId,
+    ) -> Option<Vec<D>> {
+        let result = ShortestPaths::on(graph).dijkstra().run(start).unwrap();
+        let distances = graph
+            .vertices_by_id()
+            .map(|v| result.dist(v).copied().unwrap_or(D::INFINITY))
+            .collect();
+
+        Some(distances)
+    }
+
+    fn connected_components<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<Vec<usize>> {
+        // There is no algorithm for the components, but the visited set of
+        // breadth-first search is kept between the roots, so a new root is
+        // taken only for a new component.
+        let mut labels = vec![0; graph.vertex_count()];
+        let mut bfs = Bfs::new(graph);
+        let mut count = 0;
+
+        for root in graph.vertices_by_id() {
+            if bfs.visited().is_visited(&root) {
+                continue;
+            }
+
+            for v in bfs.start(root).iter(graph) {
+                labels[v.as_usize()] = count;
+            }
+
+            count += 1;
+        }
+
+        Some(labels)
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        let mut graph = Graph::new_directed();
+
+        for package in packages.iter() {
+            graph.add_vertex(package);
+        }
+
+        graph
+    }
+
+    fn connect_packages(mut graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        graph.connect_vertices(|u, v| v.has_dependency(u).then_some(()));
+        graph
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        (graph.vertex_count(), graph.edge_count())
+    }
+
+    fn toposort<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        let sorted = TopoSort::on(graph)
+            .run()
+            .map(|r| r.map(|v| graph[v].id.as_str()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        Some(sorted)
+    }
+
+    fn topological_levels<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<Vec<&'g str>>> {
+        let mut level = vec![0; graph.vertex_count()];
+        let mut levels: Vec<Vec<&str>> = Vec::new();
+
+        for v in TopoSort::on(graph).run() {
+            let v = v.unwrap();
+            let l = level[v.as_usize()];
+
+            for n in graph.neighbors_directed(v, Direction::Outgoing) {
+                let w = n.id().as_usize();
+                level[w] = level[w].max(l + 1);
+            }
+
+            if l == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[l].push(graph[v].id.as_str());
+        }
+
+        Some(levels)
+    }
+
+    fn find_cycle<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        // The default algorithm (Kahn's) may report an edge that only leads
+        // from a cycle, the back edge found by DFS is always on the cycle.
+        let toposort::Error::Cycle(cycle) = TopoSort::on(graph)
+            .dfs()
+            .run()
+            .into_iter(graph)
+            .find_map(Result::err)
+            .unwrap();
+
+        // The error contains an edge on the cycle, from which the whole cycle
+        // can be collected. The order of the collected edges is not specified,
+        // so the cycle is followed from the reported edge.
+        let (start, _) = graph.endpoints(cycle.edge).unwrap();
+        let next = cycle
+            .collect(graph)
+            .into_iter()
+            .map(|e| graph.endpoints(e).unwrap())
+            .collect::<HashMap<_, _>>();
+
+        let mut v = start;
+        let mut cycle = Vec::with_capacity(next.len());
+
+        loop {
+            cycle.push(graph[v].id.as_str());
+            v = next[&v];
+
+            if v == start {
+                break;
+            }
+        }
+
+        Some(cycle)
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use pathfinding::{
+    directed::{
+        astar::astar,
+        bfs::bfs_loop,
+        dijkstra::{dijkstra, dijkstra_all},
+        topological_sort::{topological_sort, topological_sort_into_groups},
+    },
+    undirected::{
+        connected_components::{component_index, connected_components},
+        kruskal::kruskal,
+    },
+};
+
+use crate::{exercise::Phase, City, CityGraph, CityPath, Distance, GraphLibrary, Package};
+
+pub struct Pathfinding;
+
+struct Vertex<D: Distance> {
+    id: u64,
+    // Position of the city in the input.
+    index: usize,
+    neighbors: Vec<(u64, D::Ordered)>,
+}
+
+pub struct PathfindingCities<'a, D: Distance> {
+    cities: &'a [City],
+    vertices: HashMap<u64, Vertex<D>>,
+}
+
+pub struct PathfindingPackages<'a> {
+    packages: &'a [Package],
+    inverse_deps: HashMap<&'a str, Vec<&'a str>>,
+    roots: Vec<&'a str>,
+}
+
+impl GraphLibrary for Pathfinding {
+    const NAME: &'static str = "pathfinding";
+    const ALGORITHMS: &'static [Phase] = &[
+        Phase::Dijkstra,
+        Phase::DijkstraWithGoal,
+        Phase::ShortestPath,
+        Phase::Astar,
+        Phase::SpanningForest,
+        Phase::ConnectedComponents,
+        Phase::TopologicalSort,
+        Phase::TopologicalLevels,
+        Phase::FindCycle,
+    ];
+
+    type Cities<'a, D: Distance> = PathfindingCities<'a, D>;
+    type CityId = u64;
+    type Packages<'a> = PathfindingPackages<'a>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        let mut vertices = HashMap::with_capacity(input.cities.len());
+
+        for (index, city) in input.cities.iter().enumerate() {
+            let v = Vertex {
+                id: city.id,
+                index,
+                neighbors: Vec::new(),
+            };
+            vertices.insert(v.id, v);
+        }
+
+        PathfindingCities {
+            cities: &input.cities,
+            vertices,
+        }
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        mut graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        let cities = graph.cities;
+
+        for &(i, j, dist) in input.edges.iter() {
+            let (src, dst) = (&cities[i], &cities[j]);
+
+            let v = graph.vertices.get_mut(&src.id).unwrap();
+            v.neighbors.push((dst.id, dist.into()));
+
+            let v = graph.vertices.get_mut(&dst.id).unwrap();
+            v.neighbors.push((src.id, dist.into()));
+        }
+
+        graph
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        // Each edge is stored in both of its vertices.
+        let edges = graph
+            .vertices
+            .values()
+            .map(|v| v.neighbors.len())
+            .sum::<usize>();
+        (graph.vertices.len(), edges / 2)
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(u64, u64)> {
+        let (start, target) = graph.cities.iter().fold((None, None), |(s, t), city| {
+            (
+                s.or((city.name == start).then_some(city.id)),
+                t.or((city.name == target).then_some(city.id)),
+            )
+        });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(graph: &Self::Cities<'_, D>, index: usize) -> u64 {
+        graph.cities[index].id
+    }
+
+    fn dijkstra<D: Distance>(graph: &Self::Cities<'_, D>, start: u64, target: u64) -> Option<D> {
+        let result = dijkstra_all(&start, |n| graph.vertices[n].neighbors.iter().copied());
+        Some(result.get(&target).map_or(D::INFINITY, |&(_, d)| d.into()))
+    }
+
+    fn dijkstra_with_goal<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: u64,
+        target: u64,
+    ) -> Option<D> {
+        let result = dijkstra(
+            &start,
+            |n| graph.vertices[n].neighbors.iter().copied(),
+            |n| *n == target,
+        );
+        Some(result.map_or(D::INFINITY, |(_, d)| d.into()))
+    }
+
+    fn shortest_path<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: u64,
+        target: u64,
+    ) -> Option<CityPath<D>> {
+        let result = dijkstra(
+            &start,
+            |n| graph.vertices[n].neighbors.iter().copied(),
+            |n| *n == target,
+        );
+        let path = result.map_or_else(CityPath::unreachable, |(cities, d)| CityPath {
+            distance: d.into(),
+            cities,
+        });
+
+        Some(path)
+    }
+
+    fn astar<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: u64,
+        target: u64,
+    ) -> Option<(D, usize)> {
+        let city = |n: &u64| &graph.cities[graph.vertices[n].index];
+        let goal = city(&target);
+        let mut visited = vec![false; graph.cities.len()];
+
+        // Every vertex taken from the queue is checked for being the goal.
+        let result = astar(
+            &start,
+            |n| graph.vertices[n].neighbors.iter().copied(),
+            |n| D::estimate(city(n), goal).into(),
+            |n| {
+                visited[graph.vertices[n].index] = true;
+                *n == target
+            },
+        );
+
+        let distance = result.map_or(D::INFINITY, |(_, d)| d.into());
+        Some((distance, visited.into_iter().filter(|&v| v).count()))
+    }
+
+    fn dijkstra_distances<D: Distance>(graph: &Self::Cities<'_, D>, start: u64) -> Option<Vec<D>> {
+        let result = dijkstra_all(&start, |n| graph.vertices[n].neighbors.iter().copied());
+        let distances = graph
+            .cities
+            .iter()
+            .map(|city| {
+                if city.id == start {
+                    // The start itself is not part of the result.
+                    D::default()
+                } else {
+                    result.get(&city.id).map_or(D::INFINITY, |&(_, d)| d.into())
+                }
+            })
+            .collect();
+
+        Some(distances)
+    }
+
+    fn spanning_forest<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<(D, usize)> {
+        // Each edge is stored in both of its vertices, but it is enough to give
+        // it once.
+        let edges = graph
+            .vertices
+            .values()
+            .flat_map(|v| {
+                v.neighbors
+                    .iter()
+                    .filter(|(n, _)| v.id < *n)
+                    .map(|&(n, d)| (v.id, n, d))
+            })
+            .collect::<Vec<_>>();
+
+        let forest = kruskal(&edges)
+            .map(|(_, _, d)| d.into())
+            .collect::<Vec<D>>();
+        let weight = forest.iter().fold(D::default(), |acc, &d| acc + d);
+
+        Some((weight, graph.vertices.len() - forest.len()))
+    }
+
+    fn connected_components<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<Vec<usize>> {
+        let starts = graph.cities.iter().map(|city| city.id).collect::<Vec<_>>();
+        let components = connected_components(&starts, |n| {
+            graph.vertices[n].neighbors.iter().map(|&(m, _)| m)
+        });
+        let index = component_index(&components);
+
+        Some(starts.iter().map(|id| index[id]).collect())
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        let inverse_deps = packages
+            .iter()
+            .map(|package| (package.id.as_str(), Vec::new()))
+            .collect();
+
+        PathfindingPackages {
+            packages,
+            inverse_deps,
+            roots: Vec::new(),
+        }
+    }
+
+    fn connect_packages(mut graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        let packages = graph.packages;
+
+        for package in packages.iter() {
+            let deps = packages
+                .iter()
+                .filter(|other| other.has_dependency(package))
+                .map(|other| other.id.as_str())
+                .collect::<Vec<_>>();
+
+            if package.deps.is_empty() {
+                graph.roots.push(package.id.as_str());
+            }
+
+            graph.inverse_deps.insert(package.id.as_str(), deps);
+        }
+
+        graph
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        let edges = graph.inverse_deps.values().map(Vec::len).sum();
+        (graph.inverse_deps.len(), edges)
+    }
+
+    fn toposort<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        let sorted =
+            topological_sort(&graph.roots, |id| graph.inverse_deps[id].iter().copied()).unwrap();
+
+        Some(sorted)
+    }
+
+    fn topological_levels<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<Vec<&'g str>>> {
+        let ids = graph
+            .packages
+            .iter()
+            .map(|package| package.id.as_str())
+            .collect::<Vec<_>>();
+        let levels =
+            topological_sort_into_groups(&ids, |id| graph.inverse_deps[id].iter().copied())
+                .unwrap();
+
+        Some(levels)
+    }
+
+    fn find_cycle<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        // Seeded with all packages, as a cycle is not reachable from the roots
+        // if all its packages have dependencies only in the cycle.
+        let ids = graph
+            .packages
+            .iter()
+            .map(|package| package.id.as_str())
+            .collect::<Vec<_>>();
+        let v = topological_sort(&ids, |id| graph.inverse_deps[id].iter().copied()).unwrap_err();
+
+        // The loop starts and ends in the vertex.
+        let mut cycle = bfs_loop(&v, |id| graph.inverse_deps[id].iter().copied()).unwrap();
+        cycle.pop();
+
+        Some(cycle)
+    }
+}
@@ -0,0 +1,257 @@
+use petgraph::{
+    algo::{astar, dijkstra, min_spanning_tree, toposort},
+    data::Element,
+    graph::{DiGraph, NodeIndex, UnGraph},
+    unionfind::UnionFind,
+    visit::{EdgeRef, IntoNodeReferences, NodeRef},
+};
+
+use crate::{exercise::Phase, City, CityGraph, CityPath, Distance, GraphLibrary, Package};
+
+pub struct Petgraph;
+
+impl GraphLibrary for Petgraph {
+    const NAME: &'static str = "petgraph";
+    const ALGORITHMS: &'static [Phase] = &[
+        Phase::Dijkstra,
+        Phase::DijkstraWithGoal,
+        Phase::ShortestPath,
+        Phase::Astar,
+        Phase::SpanningForest,
+        Phase::ConnectedComponents,
+        Phase::TopologicalSort,
+        Phase::TopologicalLevels,
+        Phase::FindCycle,
+    ];
+
+    type Cities<'a, D: Distance> = UnGraph<&'a City, D>;
+    type CityId = NodeIndex;
+    type Packages<'a> = DiGraph<&'a Package, ()>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        let mut graph = UnGraph::default();
+
+        for city in input.cities.iter() {
+            graph.add_node(city);
+        }
+
+        graph
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        mut graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        for &(i, j, edge) in input.edges.iter() {
+            graph.add_edge(NodeIndex::new(i), NodeIndex::new(j), edge);
+        }
+
+        graph
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        (graph.node_count(), graph.edge_count())
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(NodeIndex, NodeIndex)> {
+        let (start, target) = graph.node_references().fold((None, None), |(s, t), v| {
+            let name = &v.weight().name;
+            (
+                s.or((*name == start).then_some(v.id())),
+                t.or((*name == target).then_some(v.id())),
+            )
+        });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(_graph: &Self::Cities<'_, D>, index: usize) -> NodeIndex {
+        NodeIndex::new(index)
+    }
+
+    fn dijkstra<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: NodeIndex,
+        target: NodeIndex,
+    ) -> Option<D> {
+        let result = dijkstra(graph, start, None, |e| *e.weight());
+        Some(result.get(&target).copied().unwrap_or(D::INFINITY))
+    }
+
+    fn dijkstra_with_goal<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: NodeIndex,
+        target: NodeIndex,
+    ) -> Option<D> {
+        let result = dijkstra(graph, start, Some(target), |e| *e.weight());
+        Some(result.get(&target).copied().unwrap_or(D::INFINITY))
+    }
+
+    fn shortest_path<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: NodeIndex,
+        target: NodeIndex,
+    ) -> Option<CityPath<D>> {
+        // Dijkstra does not keep the predecessors, A* with zero estimate is
+        // equivalent and returns the path.
+        let result = astar(
+            graph,
+            start,
+            |v| v == target,
+            |e| *e.weight(),
+            |_| D::default(),
+        );
+        let path = result.map_or_else(CityPath::unreachable, |(distance, path)| CityPath {
+            distance,
+            cities: path.into_iter().map(|v| graph[v].id).collect(),
+        });
+
+        Some(path)
+    }
+
+    fn astar<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: NodeIndex,
+        target: NodeIndex,
+    ) -> Option<(D, usize)> {
+        let mut visited = vec![false; graph.node_count()];
+
+        // Every vertex taken from the queue is checked for being the goal.
+        let result = astar(
+            graph,
+            start,
+            |v| {
+                visited[v.index()] = true;
+                v == target
+            },
+            |e| *e.weight(),
+            |v| D::estimate(graph[v], graph[target]),
+        );
+
+        let distance = result.map_or(D::INFINITY, |(d, _)| d);
+        Some((distance, visited.into_iter().filter(|&v| v).count()))
+    }
+
+    fn dijkstra_distances<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: NodeIndex,
+    ) -> Option<Vec<D>> {
+        let result = dijkstra(graph, start, None, |e| *e.weight());
+        let distances = graph
+            .node_indices()
+            .map(|v| result.get(&v).copied().unwrap_or(D::INFINITY))
+            .collect();
+
+        Some(distances)
+    }
+
+    fn spanning_forest<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<(D, usize)> {
+        let mut weight = D::default();
+        let mut edges = 0;
+
+        // The forest is yielded as all the vertices followed by the edges.
+        for element in min_spanning_tree(graph) {
+            if let Element::Edge { weight: w, .. } = element {
+                weight = weight + w;
+                edges += 1;
+            }
+        }
+
+        Some((weight, graph.node_count() - edges))
+    }
+
+    fn connected_components<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<Vec<usize>> {
+        // Function `connected_components` only counts the components, so the
+        // union-find structure it uses is used directly.
+        let mut components = UnionFind::new(graph.node_count());
+
+        for edge in graph.edge_references() {
+            components.union(edge.source().index(), edge.target().index());
+        }
+
+        Some(components.into_labeling())
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        let mut graph = DiGraph::default();
+
+        for package in packages.iter() {
+            graph.add_node(package);
+        }
+
+        graph
+    }
+
+    fn connect_packages(mut graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        for i in 0..graph.node_count() {
+            for j in 0..graph.node_count() {
+                let i = NodeIndex::from(i as u32);
+                let j = NodeIndex::from(j as u32);
+
+                let src = &graph[i];
+                let dst = &graph[j];
+
+                if dst.has_dependency(src) {
+                    graph.add_edge(i, j, ());
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        (graph.node_count(), graph.edge_count())
+    }
+
+    fn toposort<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        let sorted = toposort(graph, None)
+            .unwrap()
+            .into_iter()
+            .map(|v| graph[v].id.as_str())
+            .collect();
+
+        Some(sorted)
+    }
+
+    fn topological_levels<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<Vec<&'g str>>> {
+        let mut level = vec![0; graph.node_count()];
+        let mut levels: Vec<Vec<&str>> = Vec::new();
+
+        for v in toposort(graph, None).unwrap() {
+            let l = level[v.index()];
+
+            for w in graph.neighbors(v) {
+                level[w.index()] = level[w.index()].max(l + 1);
+            }
+
+            if l == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[l].push(graph[v].id.as_str());
+        }
+
+        Some(levels)
+    }
+
+    fn find_cycle<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        let v = toposort(graph, None).unwrap_err().node_id();
+
+        // The error contains only a vertex on the cycle, the way back to it
+        // needs to be searched for.
+        let (_, mut path) = graph
+            .neighbors(v)
+            .filter_map(|w| astar(graph, w, |n| n == v, |_| 1, |_| 0))
+            .min_by_key(|(cost, _)| *cost)
+            .unwrap();
+
+        // The path ends in the vertex, move it to the beginning.
+        path.rotate_right(1);
+
+        Some(path.into_iter().map(|v| graph[v].id.as_str()).collect())
+    }
+}
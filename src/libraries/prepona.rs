@@ -0,0 +1,209 @@
+use prepona::prelude::*;
+use prepona::{
+    algo::{ConnectedComponents, Dijkstra, Kruskal, TopologicalSort},
+    graph::{DefaultEdge, DirectedEdge, ListGraph, SimpleGraph, UndirectedEdge},
+    storage::{DiList, List},
+};
+
+use crate::{exercise::Phase, City, CityGraph, Distance, GraphLibrary, Package};
+
+pub struct Prepona;
+
+pub struct PreponaCities<'a, D: Distance> {
+    cities: &'a [City],
+    graph: ListGraph<D::Unsigned, UndirectedEdge>,
+}
+
+pub struct PreponaPackages<'a> {
+    packages: &'a [Package],
+    graph: ListGraph<(), DirectedEdge>,
+}
+
+impl GraphLibrary for Prepona {
+    const NAME: &'static str = "prepona";
+    const ALGORITHMS: &'static [Phase] = &[
+        Phase::Dijkstra,
+        Phase::SpanningForest,
+        Phase::ConnectedComponents,
+        Phase::TopologicalSort,
+        Phase::TopologicalLevels,
+    ];
+
+    type Cities<'a, D: Distance> = PreponaCities<'a, D>;
+    type CityId = usize;
+    type Packages<'a> = PreponaPackages<'a>;
+
+    fn add_cities<D: Distance>(input: &CityGraph<D>) -> Self::Cities<'_, D> {
+        // Using a custom wrapper over floats, because `ordered_float::OrderedFloat`
+        // does not implement `num_traits::Unsigned`.
+        let mut graph = SimpleGraph::init(List::<D::Unsigned>::init());
+
+        for _ in input.cities.iter() {
+            graph.add_vertex();
+        }
+
+        PreponaCities {
+            cities: &input.cities,
+            graph,
+        }
+    }
+
+    fn connect_cities<'a, D: Distance>(
+        mut graph: Self::Cities<'a, D>,
+        input: &'a CityGraph<D>,
+    ) -> Self::Cities<'a, D> {
+        for &(i, j, edge) in input.edges.iter() {
+            let edge = <D::Unsigned as TryFrom<D>>::try_from(edge).unwrap();
+            graph.graph.add_edge(i, j, DefaultEdge::from(edge)).unwrap();
+        }
+
+        graph
+    }
+
+    fn cities_size<D: Distance>(graph: &Self::Cities<'_, D>) -> (usize, usize) {
+        (graph.graph.vertex_count(), graph.graph.edges_count())
+    }
+
+    fn find_cities<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: &str,
+        target: &str,
+    ) -> Option<(usize, usize)> {
+        let (start, target) = graph
+            .graph
+            .vertices()
+            .into_iter()
+            .fold((None, None), |(s, t), v| {
+                let name = &graph.cities[v].name;
+                (
+                    s.or((*name == start).then_some(v)),
+                    t.or((*name == target).then_some(v)),
+                )
+            });
+
+        Some((start?, target?))
+    }
+
+    fn city_id<D: Distance>(_graph: &Self::Cities<'_, D>, index: usize) -> usize {
+        index
+    }
+
+    fn dijkstra<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: usize,
+        target: usize,
+    ) -> Option<D> {
+        let result = Dijkstra::init(&graph.graph).execute(&graph.graph, start);
+        let distance = result
+            .distance_to(target)
+            .and_then(|d| d.as_ref().map(|&d| d.into()))
+            .unwrap_or(D::INFINITY);
+
+        Some(distance)
+    }
+
+    fn dijkstra_distances<D: Distance>(
+        graph: &Self::Cities<'_, D>,
+        start: usize,
+    ) -> Option<Vec<D>> {
+        let result = Dijkstra::init(&graph.graph).execute(&graph.graph, start);
+        let distances = (0..graph.cities.len())
+            .map(|v| {
+                result
+                    .distance_to(v)
+                    .and_then(|d| d.as_ref().map(|&d| d.into()))
+                    .unwrap_or(D::INFINITY)
+            })
+            .collect();
+
+        Some(distances)
+    }
+
+    fn spanning_forest<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<(D, usize)> {
+        let forest = Kruskal::init(&graph.graph).execute(&graph.graph);
+        // Isolated vertices are not part of the subgraph, so the components are
+        // counted from all vertices of the graph.
+        let edges = forest.edges();
+        let weight = edges.iter().fold(D::default(), |acc, (_, _, edge)| {
+            acc + (*edge.get_weight().as_ref().unwrap()).into()
+        });
+
+        Some((weight, graph.cities.len() - edges.len()))
+    }
+
+    fn connected_components<D: Distance>(graph: &Self::Cities<'_, D>) -> Option<Vec<usize>> {
+        let components = ConnectedComponents::init(&graph.graph).execute(&graph.graph);
+        let mut labels = vec![0; graph.cities.len()];
+
+        for (label, component) in components.iter().enumerate() {
+            for &v in component.iter() {
+                labels[v] = label;
+            }
+        }
+
+        Some(labels)
+    }
+
+    fn add_packages(packages: &[Package]) -> Self::Packages<'_> {
+        let mut graph = SimpleGraph::init(DiList::<()>::init());
+
+        for _ in packages.iter() {
+            graph.add_vertex();
+        }
+
+        PreponaPackages { packages, graph }
+    }
+
+    fn connect_packages(mut graph: Self::Packages<'_>) -> Self::Packages<'_> {
+        let packages = graph.packages;
+
+        for i in 0..graph.graph.vertex_count() {
+            let src = &packages[i];
+
+            #[allow(clippy::needless_range_loop)]
+            for j in 0..graph.graph.vertex_count() {
+                let dst = &packages[j];
+
+                if dst.has_dependency(src) {
+                    graph.graph.add_edge(i, j, ().into()).unwrap();
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn packages_size(graph: &Self::Packages<'_>) -> (usize, usize) {
+        (graph.graph.vertex_count(), graph.graph.edges_count())
+    }
+
+    fn toposort<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<&'g str>> {
+        let sorted = TopologicalSort::init()
+            .execute(&graph.graph)
+            .into_iter()
+            .map(|v| graph.packages[v].id.as_str())
+            .collect();
+
+        Some(sorted)
+    }
+
+    fn topological_levels<'g>(graph: &'g Self::Packages<'_>) -> Option<Vec<Vec<&'g str>>> {
+        let mut level = vec![0; graph.graph.vertex_count()];
+        let mut levels: Vec<Vec<&str>> = Vec::new();
+
+        for v in TopologicalSort::init().execute(&graph.graph) {
+            let l = level[v];
+
+            for w in graph.graph.neighbors_unchecked(v) {
+                level[w] = level[w].max(l + 1);
+            }
+
+            if l == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[l].push(graph.packages[v].id.as_str());
+        }
+
+        Some(levels)
+    }
+}
@@ -0,0 +1,881 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt,
+};
+
+use crate::{City, CityGraph, CityPath, Distance, Package};
+
+// All pairs `(i, j, distance)` with `i < j` of connected cities, found by
+// scanning all pairs.
+pub fn city_pairs(cities: &[City]) -> Vec<(usize, usize, f32)> {
+    let mut pairs = Vec::new();
+
+    for i in 0..cities.len() {
+        for j in (i + 1)..cities.len() {
+            if let Some(d) = cities[i].are_connected(&cities[j]) {
+                pairs.push((i, j, d));
+            }
+        }
+    }
+
+    pairs
+}
+
+// Neighbors with distances of every city, from the edges of the graph.
+pub fn graph_neighbors<D: Distance>(graph: &CityGraph<D>) -> Vec<Vec<(usize, D)>> {
+    let mut neighbors = vec![Vec::new(); graph.cities.len()];
+
+    for &(i, j, d) in graph.edges.iter() {
+        neighbors[i].push((j, d));
+        neighbors[j].push((i, d));
+    }
+
+    neighbors
+}
+
+// Distances from the start to all cities. Unreachable cities have infinite
+// distance.
+pub fn dijkstra<D: Distance>(graph: &CityGraph<D>, start: usize) -> Vec<D> {
+    dijkstra_on(&graph_neighbors(graph), start)
+}
+
+pub fn dijkstra_on<D: Distance>(neighbors: &[Vec<(usize, D)>], start: usize) -> Vec<D> {
+    let mut dist = vec![D::INFINITY; neighbors.len()];
+    let mut queue = BinaryHeap::new();
+
+    dist[start] = D::default();
+    queue.push(Reverse((D::Ordered::from(D::default()), start)));
+
+    while let Some(Reverse((d, u))) = queue.pop() {
+        let d: D = d.into();
+        if d > dist[u] {
+            continue;
+        }
+
+        for &(v, w) in neighbors[u].iter() {
+            let alt = d + w;
+            if alt < dist[v] {
+                dist[v] = alt;
+                queue.push(Reverse((alt.into(), v)));
+            }
+        }
+    }
+
+    dist
+}
+
+// Distance from the start to the target found by A* with `Distance::estimate`
+// as the heuristic, and the number of distinct vertices taken from the queue.
+pub fn astar<D: Distance>(graph: &CityGraph<D>, start: usize, target: usize) -> (D, usize) {
+    let cities = &graph.cities;
+    let neighbors = graph_neighbors(graph);
+    let estimate = |v: usize| D::estimate(&cities[v], &cities[target]);
+
+    let mut dist = vec![D::INFINITY; cities.len()];
+    let mut visited = vec![false; cities.len()];
+    let mut queue = BinaryHeap::new();
+
+    dist[start] = D::default();
+    queue.push(Reverse((D::Ordered::from(estimate(start)), start)));
+
+    while let Some(Reverse((f, u))) = queue.pop() {
+        // A vertex is taken again only if a shorter path to it was found.
+        let f: D = f.into();
+        if f > dist[u] + estimate(u) {
+            continue;
+        }
+
+        visited[u] = true;
+
+        if u == target {
+            break;
+        }
+
+        for &(v, w) in neighbors[u].iter() {
+            let alt = dist[u] + w;
+            if alt < dist[v] {
+                dist[v] = alt;
+                queue.push(Reverse(((alt + estimate(v)).into(), v)));
+            }
+        }
+    }
+
+    (dist[target], visited.iter().filter(|&&v| v).count())
+}
+
+// Distance from the start to the target found by searching from both of them
+// at once, and the number of vertices settled by the two searches together.
+// The graph is undirected, so both searches use the same neighbors.
+pub fn bidirectional_dijkstra<D: Distance>(
+    neighbors: &[Vec<(usize, D)>],
+    start: usize,
+    target: usize,
+) -> (D, usize) {
+    let n = neighbors.len();
+    let mut dist = [vec![D::INFINITY; n], vec![D::INFINITY; n]];
+    let mut settled = [vec![false; n], vec![false; n]];
+    let mut queues = [BinaryHeap::new(), BinaryHeap::new()];
+    let mut count = 0;
+
+    let mut best = if start == target {
+        D::default()
+    } else {
+        D::INFINITY
+    };
+
+    for (side, v) in [start, target].into_iter().enumerate() {
+        dist[side][v] = D::default();
+        queues[side].push(Reverse((D::Ordered::from(D::default()), v)));
+    }
+
+    loop {
+        for side in 0..2 {
+            while let Some(&Reverse((_, u))) = queues[side].peek() {
+                if !settled[side][u] {
+                    break;
+                }
+                queues[side].pop();
+            }
+        }
+
+        // An exhausted search has settled the whole component of its vertex.
+        let (Some(&Reverse((f, _))), Some(&Reverse((b, _)))) = (queues[0].peek(), queues[1].peek())
+        else {
+            break;
+        };
+
+        // Any shorter path would have to go through vertices that neither of
+        // the searches has settled.
+        let (f, b): (D, D) = (f.into(), b.into());
+        if f + b >= best {
+            break;
+        }
+
+        let side = if f <= b { 0 } else { 1 };
+        let Reverse((d, u)) = queues[side].pop().unwrap();
+        let d: D = d.into();
+
+        settled[side][u] = true;
+        count += 1;
+
+        for &(v, w) in neighbors[u].iter() {
+            let alt = d + w;
+            if alt < dist[side][v] {
+                dist[side][v] = alt;
+                queues[side].push(Reverse((alt.into(), v)));
+            }
+
+            let other = dist[1 - side][v];
+            if other != D::INFINITY && alt + other < best {
+                best = alt + other;
+            }
+        }
+    }
+
+    (best, count)
+}
+
+// Total weight of the minimum spanning forest found by Kruskal's algorithm and
+// the number of its trees.
+pub fn kruskal<D: Distance>(graph: &CityGraph<D>) -> (D, usize) {
+    fn find(parent: &mut [usize], mut v: usize) -> usize {
+        while parent[v] != v {
+            parent[v] = parent[parent[v]];
+            v = parent[v];
+        }
+        v
+    }
+
+    let mut edges = graph.edges.clone();
+    edges.sort_by_key(|&(_, _, d)| D::Ordered::from(d));
+
+    let mut parent = (0..graph.cities.len()).collect::<Vec<_>>();
+    let mut weight = D::default();
+    let mut components = graph.cities.len();
+
+    for (i, j, d) in edges {
+        let (i, j) = (find(&mut parent, i), find(&mut parent, j));
+        if i != j {
+            parent[i] = j;
+            weight = weight + d;
+            components -= 1;
+        }
+    }
+
+    (weight, components)
+}
+
+// Component of each city found by breadth-first search, numbered in the order
+// of their first cities.
+pub fn connected_components<D: Distance>(graph: &CityGraph<D>) -> Vec<usize> {
+    let neighbors = graph_neighbors(graph);
+    let mut labels = vec![usize::MAX; neighbors.len()];
+    let mut queue = VecDeque::new();
+    let mut count = 0;
+
+    for root in 0..neighbors.len() {
+        if labels[root] != usize::MAX {
+            continue;
+        }
+
+        labels[root] = count;
+        queue.push_back(root);
+
+        while let Some(u) = queue.pop_front() {
+            for &(v, _) in neighbors[u].iter() {
+                if labels[v] == usize::MAX {
+                    labels[v] = count;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    labels
+}
+
+// First city that is not in the same component with the same cities in both
+// labelings, which may use different labels.
+pub fn components_mismatch(expected: &[usize], actual: &[usize]) -> Option<usize> {
+    assert_eq!(expected.len(), actual.len());
+
+    let mut forward = HashMap::new();
+    let mut backward = HashMap::new();
+
+    (0..expected.len()).find(|&i| {
+        *forward.entry(expected[i]).or_insert(actual[i]) != actual[i]
+            || *backward.entry(actual[i]).or_insert(expected[i]) != expected[i]
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceMismatch<D> {
+    pub index: usize,
+    pub expected: D,
+    pub actual: D,
+}
+
+// Distances are compared with the relative tolerance of their type.
+pub fn verify_distances<D: Distance>(expected: &[D], actual: &[D]) -> Vec<DistanceMismatch<D>> {
+    assert_eq!(expected.len(), actual.len());
+
+    expected
+        .iter()
+        .zip(actual.iter())
+        .enumerate()
+        .filter(|(_, (&expected, &actual))| distances_differ(expected, actual))
+        .map(|(index, (&expected, &actual))| DistanceMismatch {
+            index,
+            expected,
+            actual,
+        })
+        .collect()
+}
+
+pub fn distances_differ<D: Distance>(expected: D, actual: D) -> bool {
+    if expected == D::INFINITY || actual == D::INFINITY {
+        expected != actual
+    } else {
+        let (expected, actual) = (expected.to_f64(), actual.to_f64());
+        (expected - actual).abs() > D::TOLERANCE * expected.max(actual).max(1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError<D> {
+    // The path has no cities, but a finite distance.
+    Empty,
+    Unknown(u64),
+    Start(u64),
+    Target(u64),
+    NotConnected { from: u64, to: u64 },
+    // The edges on the path do not sum to the reported distance.
+    Distance { sum: D, reported: D },
+}
+
+impl<D: fmt::Display> fmt::Display for PathError<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::Empty => write!(f, "empty path to a reachable target"),
+            PathError::Unknown(id) => write!(f, "unknown city {id}"),
+            PathError::Start(id) => write!(f, "path starts at city {id}, not the start"),
+            PathError::Target(id) => write!(f, "path ends at city {id}, not the target"),
+            PathError::NotConnected { from, to } => {
+                write!(f, "cities {from} and {to} are not connected")
+            }
+            PathError::Distance { sum, reported } => {
+                write!(f, "edges sum to {sum}, but the distance is {reported}")
+            }
+        }
+    }
+}
+
+impl<D: fmt::Debug + fmt::Display> std::error::Error for PathError<D> {}
+
+// Checks that the path goes from the start to the target (indices in the
+// graph) using only edges of the graph, which sum to the reported distance.
+pub fn verify_path<D: Distance>(
+    graph: &CityGraph<D>,
+    start: usize,
+    target: usize,
+    path: &CityPath<D>,
+) -> Result<(), PathError<D>> {
+    if path.cities.is_empty() {
+        return if path.distance == D::INFINITY {
+            Ok(())
+        } else {
+            Err(PathError::Empty)
+        };
+    }
+
+    let index = graph
+        .cities
+        .iter()
+        .enumerate()
+        .map(|(i, city)| (city.id, i))
+        .collect::<HashMap<_, _>>();
+    let edges = graph
+        .edges
+        .iter()
+        .map(|&(i, j, d)| ((i, j), d))
+        .collect::<HashMap<_, _>>();
+
+    let cities = path
+        .cities
+        .iter()
+        .map(|id| index.get(id).copied().ok_or(PathError::Unknown(*id)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if cities[0] != start {
+        return Err(PathError::Start(path.cities[0]));
+    }
+
+    if cities[cities.len() - 1] != target {
+        return Err(PathError::Target(path.cities[cities.len() - 1]));
+    }
+
+    let mut sum = D::default();
+
+    for (k, pair) in cities.windows(2).enumerate() {
+        let key = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+        let Some(&d) = edges.get(&key) else {
+            return Err(PathError::NotConnected {
+                from: path.cities[k],
+                to: path.cities[k + 1],
+            });
+        };
+
+        sum = sum + d;
+    }
+
+    if distances_differ(sum, path.distance) {
+        return Err(PathError::Distance {
+            sum,
+            reported: path.distance,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+    Missing(String),
+    Duplicate(String),
+    Unknown(String),
+    Dependency { package: String, dependency: String },
+    // The package could be in an earlier level.
+    NotEarliest(String),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::Missing(name) => write!(f, "package `{name}` is missing"),
+            OrderError::Duplicate(name) => write!(f, "package `{name}` is duplicated"),
+            OrderError::Unknown(name) => write!(f, "package `{name}` is unknown"),
+            OrderError::Dependency {
+                package,
+                dependency,
+            } => write!(
+                f,
+                "package `{package}` is not ordered after its dependency `{dependency}`"
+            ),
+            OrderError::NotEarliest(name) => {
+                write!(f, "package `{name}` could be in an earlier level")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+pub fn verify_topological_order(packages: &[Package], order: &[&str]) -> Result<(), OrderError> {
+    let ids = packages
+        .iter()
+        .map(|package| package.id.as_str())
+        .collect::<HashSet<_>>();
+    let mut position = HashMap::with_capacity(order.len());
+
+    for (i, &id) in order.iter().enumerate() {
+        if !ids.contains(id) {
+            return Err(OrderError::Unknown(id.to_string()));
+        }
+
+        if position.insert(id, i).is_some() {
+            return Err(OrderError::Duplicate(id.to_string()));
+        }
+    }
+
+    for package in packages {
+        let Some(&i) = position.get(package.id.as_str()) else {
+            return Err(OrderError::Missing(package.id.clone()));
+        };
+
+        for dep in package.deps.keys() {
+            if position.get(dep.as_str()).is_some_and(|&j| j > i) {
+                return Err(OrderError::Dependency {
+                    package: package.id.clone(),
+                    dependency: dep.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Packages grouped into levels for a parallel build, each level depending only
+// on the earlier ones. Every package is in the level right after the last level
+// of its dependencies, so the number of levels is the length of the critical
+// path. `None` if the dependencies contain a cycle.
+pub fn topological_levels(packages: &[Package]) -> Option<Vec<Vec<&str>>> {
+    let index = packages
+        .iter()
+        .enumerate()
+        .map(|(i, package)| (package.id.as_str(), i))
+        .collect::<HashMap<_, _>>();
+
+    let mut remaining = vec![0; packages.len()];
+    let mut dependents = vec![Vec::new(); packages.len()];
+
+    for (i, package) in packages.iter().enumerate() {
+        for dep in package.deps.keys() {
+            if let Some(&j) = index.get(dep.as_str()) {
+                remaining[i] += 1;
+                dependents[j].push(i);
+            }
+        }
+    }
+
+    let mut level = (0..packages.len())
+        .filter(|&i| remaining[i] == 0)
+        .collect::<Vec<_>>();
+    let mut levels = Vec::new();
+    let mut visited = 0;
+
+    while !level.is_empty() {
+        visited += level.len();
+
+        let mut next = Vec::new();
+        for &i in level.iter() {
+            for &j in dependents[i].iter() {
+                remaining[j] -= 1;
+                if remaining[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+
+        levels.push(level.iter().map(|&i| packages[i].id.as_str()).collect());
+        level = next;
+    }
+
+    (visited == packages.len()).then_some(levels)
+}
+
+pub fn verify_topological_levels(
+    packages: &[Package],
+    levels: &[Vec<&str>],
+) -> Result<(), OrderError> {
+    let ids = packages
+        .iter()
+        .map(|package| package.id.as_str())
+        .collect::<HashSet<_>>();
+    let mut position = HashMap::with_capacity(packages.len());
+
+    for (i, level) in levels.iter().enumerate() {
+        for &id in level.iter() {
+            if !ids.contains(id) {
+                return Err(OrderError::Unknown(id.to_string()));
+            }
+
+            if position.insert(id, i).is_some() {
+                return Err(OrderError::Duplicate(id.to_string()));
+            }
+        }
+    }
+
+    for package in packages {
+        let Some(&i) = position.get(package.id.as_str()) else {
+            return Err(OrderError::Missing(package.id.clone()));
+        };
+
+        let mut earliest = 0;
+
+        for dep in package.deps.keys() {
+            let Some(&j) = position.get(dep.as_str()) else {
+                continue;
+            };
+
+            if j >= i {
+                return Err(OrderError::Dependency {
+                    package: package.id.clone(),
+                    dependency: dep.clone(),
+                });
+            }
+
+            earliest = earliest.max(j + 1);
+        }
+
+        if i > earliest {
+            return Err(OrderError::NotEarliest(package.id.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+// Some cycle in the dependencies, each package being a dependency of the next
+// one, found by depth-first search. `None` if there is no cycle.
+pub fn find_cycle(packages: &[Package]) -> Option<Vec<&str>> {
+    let index = packages
+        .iter()
+        .enumerate()
+        .map(|(i, package)| (package.id.as_str(), i))
+        .collect::<HashMap<_, _>>();
+
+    let deps = packages
+        .iter()
+        .map(|package| {
+            package
+                .deps
+                .keys()
+                .filter_map(|dep| index.get(dep.as_str()).copied())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Packages on the current path are on the stack, finished packages are
+    // known not to lead to a cycle.
+    let mut on_stack = vec![false; packages.len()];
+    let mut finished = vec![false; packages.len()];
+
+    for start in 0..packages.len() {
+        if finished[start] {
+            continue;
+        }
+
+        let mut stack = vec![(start, 0)];
+        on_stack[start] = true;
+
+        while let Some((i, next)) = stack.last_mut() {
+            let i = *i;
+
+            let Some(&j) = deps[i].get(*next) else {
+                stack.pop();
+                on_stack[i] = false;
+                finished[i] = true;
+                continue;
+            };
+
+            *next += 1;
+
+            if on_stack[j] {
+                // The path from `j` goes through dependencies back to `j`.
+                let position = stack.iter().position(|&(k, _)| k == j).unwrap();
+                return Some(
+                    stack[position..]
+                        .iter()
+                        .rev()
+                        .map(|&(k, _)| packages[k].id.as_str())
+                        .collect(),
+                );
+            }
+
+            if !finished[j] {
+                on_stack[j] = true;
+                stack.push((j, 0));
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CycleError {
+    Empty,
+    Unknown(String),
+    Duplicate(String),
+    NotDependency { package: String, dependent: String },
+    // The cycle does not go through the injected dependency.
+    NotInjected { package: String, dependent: String },
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CycleError::Empty => write!(f, "cycle is empty"),
+            CycleError::Unknown(name) => write!(f, "package `{name}` is unknown"),
+            CycleError::Duplicate(name) => write!(f, "package `{name}` is duplicated"),
+            CycleError::NotDependency { package, dependent } => write!(
+                f,
+                "package `{package}` is not a dependency of the next package `{dependent}`"
+            ),
+            CycleError::NotInjected { package, dependent } => write!(
+                f,
+                "cycle does not go through the injected dependency of `{dependent}` on `{package}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+// Checks that each package in the cycle is a dependency of the next one, and
+// the last one is a dependency of the first one.
+pub fn verify_cycle(packages: &[Package], cycle: &[&str]) -> Result<(), CycleError> {
+    let by_id = packages
+        .iter()
+        .map(|package| (package.id.as_str(), package))
+        .collect::<HashMap<_, _>>();
+    let mut seen = HashSet::with_capacity(cycle.len());
+
+    if cycle.is_empty() {
+        return Err(CycleError::Empty);
+    }
+
+    for &id in cycle.iter() {
+        if !by_id.contains_key(id) {
+            return Err(CycleError::Unknown(id.to_string()));
+        }
+
+        if !seen.insert(id) {
+            return Err(CycleError::Duplicate(id.to_string()));
+        }
+    }
+
+    for (i, &id) in cycle.iter().enumerate() {
+        let dependent = cycle[(i + 1) % cycle.len()];
+
+        if !by_id[dependent].deps.contains_key(id) {
+            return Err(CycleError::NotDependency {
+                package: id.to_string(),
+                dependent: dependent.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Checks the cycle like `verify_cycle` and that it goes through the dependency
+// closing the injected cycle (see `crate::inject_cycle`), that is, the last
+// package of the injected cycle followed by the first one. Any cycle in a tree
+// that was acyclic before the injection must go through it.
+pub fn verify_injected_cycle(
+    packages: &[Package],
+    cycle: &[&str],
+    injected: &[String],
+) -> Result<(), CycleError> {
+    verify_cycle(packages, cycle)?;
+
+    let (package, dependent) = (injected.last().unwrap(), &injected[0]);
+    let closed =
+        (0..cycle.len()).any(|i| cycle[i] == package && cycle[(i + 1) % cycle.len()] == dependent);
+
+    if closed {
+        Ok(())
+    } else {
+        Err(CycleError::NotInjected {
+            package: package.clone(),
+            dependent: dependent.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Meters;
+
+    // City on the equator at given distance in meters east of the prime
+    // meridian.
+    fn city(id: u64, meters: f64) -> City {
+        City {
+            id,
+            name: format!("city {id}"),
+            pop: 1000,
+            lat: 0.0,
+            lon: (meters / 1e3 / f64::from(City::EARTH_RADIUS)).to_degrees(),
+        }
+    }
+
+    // Path of cities at given positions, with weights from their distances.
+    fn path<D: Distance>(cities: &[City], path: &[usize]) -> Vec<(usize, usize, D)> {
+        path.windows(2)
+            .map(|w| (w[0], w[1], D::between(&cities[w[0]], &cities[w[1]])))
+            .collect()
+    }
+
+    #[test]
+    fn astar_with_rounded_weights() {
+        // Two chains of the same length from the first city to the seventh
+        // one. The first one has more edges, each rounded up, so the second
+        // one is shorter. The estimate is rounded down, so it does not exceed
+        // the rest of either of them.
+        let cities = (0..7)
+            .map(|i| city(i, i as f64 * 1000.4))
+            .chain([city(7, 2000.49), city(8, 4000.98)])
+            .collect::<Vec<_>>();
+        let mut edges = path::<Meters>(&cities, &[0, 1, 2, 3, 4, 5, 6]);
+        edges.extend(path::<Meters>(&cities, &[0, 7, 8, 6]));
+        let graph = CityGraph { cities, edges };
+
+        let distances = dijkstra(&graph, 0);
+        assert_eq!(distances[6], Meters(6004));
+
+        let (distance, _) = astar(&graph, 0, 6);
+        assert_eq!(distance, distances[6]);
+    }
+
+    // Square of cities 1 km apart with one diagonal, and a remote city.
+    fn square() -> CityGraph<Meters> {
+        let cities = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (0.0, 20.0)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (lat, lon))| City {
+                lat: (lat / f64::from(City::EARTH_RADIUS)).to_degrees(),
+                lon: (lon / f64::from(City::EARTH_RADIUS)).to_degrees(),
+                ..city(i as u64, 0.0)
+            })
+            .collect::<Vec<_>>();
+        let mut edges = path::<Meters>(&cities, &[0, 1, 2, 3]);
+        edges.extend(path::<Meters>(&cities, &[0, 3]));
+        edges.extend(path::<Meters>(&cities, &[0, 2]));
+        CityGraph { cities, edges }
+    }
+
+    #[test]
+    fn bidirectional_matches_dijkstra() {
+        let graph = square();
+        let neighbors = graph_neighbors(&graph);
+
+        for start in 0..graph.cities.len() {
+            let expected = dijkstra(&graph, start);
+
+            for (target, &expected) in expected.iter().enumerate() {
+                let (distance, _) = bidirectional_dijkstra(&neighbors, start, target);
+                assert_eq!(distance, expected, "{start} -> {target}");
+            }
+        }
+
+        assert_eq!(bidirectional_dijkstra(&neighbors, 1, 3).0, Meters(2000));
+        assert_eq!(bidirectional_dijkstra(&neighbors, 0, 4).0, Meters::INFINITY);
+        assert_eq!(bidirectional_dijkstra(&neighbors, 2, 2).0, Meters(0));
+    }
+
+    #[test]
+    fn kruskal_spanning_forest() {
+        let graph = square();
+
+        assert_eq!(kruskal(&graph), (Meters(3000), 2));
+    }
+
+    #[test]
+    fn components_of_cities() {
+        let graph = square();
+
+        let labels = connected_components(&graph);
+        assert_eq!(labels, [0, 0, 0, 0, 1]);
+
+        assert_eq!(components_mismatch(&labels, &[7, 7, 7, 7, 3]), None);
+        assert_eq!(components_mismatch(&labels, &[7, 7, 3, 7, 3]), Some(2));
+        assert_eq!(components_mismatch(&labels, &[7, 7, 7, 7, 7]), Some(4));
+    }
+
+    #[test]
+    fn levels_of_dependencies() {
+        let packages =
+            crate::tests::packages(&[("a", &["b", "c"]), ("b", &["c"]), ("c", &[]), ("d", &[])]);
+
+        let levels = topological_levels(&packages).unwrap();
+        assert_eq!(
+            levels,
+            [vec!["c@1.0.0", "d@1.0.0"], vec!["b@1.0.0"], vec!["a@1.0.0"]]
+        );
+        assert_eq!(verify_topological_levels(&packages, &levels), Ok(()));
+
+        let late = [vec!["c@1.0.0"], vec!["b@1.0.0", "d@1.0.0"], vec!["a@1.0.0"]];
+        assert_eq!(
+            verify_topological_levels(&packages, &late),
+            Err(OrderError::NotEarliest(String::from("d@1.0.0")))
+        );
+
+        let order = levels.concat();
+        assert_eq!(verify_topological_order(&packages, &order), Ok(()));
+        assert_eq!(find_cycle(&packages), None);
+    }
+
+    #[test]
+    fn cycle_in_dependencies() {
+        let packages =
+            crate::tests::packages(&[("a", &["b"]), ("b", &["c"]), ("c", &["a", "d"]), ("d", &[])]);
+
+        assert_eq!(topological_levels(&packages), None);
+
+        let cycle = find_cycle(&packages).unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(verify_cycle(&packages, &cycle), Ok(()));
+
+        assert_eq!(verify_cycle(&packages, &[]), Err(CycleError::Empty));
+        assert_eq!(
+            verify_cycle(&packages, &["a@1.0.0", "x@1.0.0"]),
+            Err(CycleError::Unknown(String::from("x@1.0.0")))
+        );
+        assert_eq!(
+            verify_cycle(&packages, &["a@1.0.0", "a@1.0.0"]),
+            Err(CycleError::Duplicate(String::from("a@1.0.0")))
+        );
+        assert_eq!(
+            verify_cycle(&packages, &["a@1.0.0", "b@1.0.0", "c@1.0.0"]),
+            Err(CycleError::NotDependency {
+                package: String::from("a@1.0.0"),
+                dependent: String::from("b@1.0.0"),
+            })
+        );
+    }
+
+    #[test]
+    fn cycle_through_injected_dependency() {
+        let packages =
+            crate::tests::packages(&[("a", &["b"]), ("b", &["a"]), ("c", &["d"]), ("d", &["c"])]);
+        let injected = [String::from("b@1.0.0"), String::from("a@1.0.0")];
+
+        assert_eq!(
+            verify_injected_cycle(&packages, &["a@1.0.0", "b@1.0.0"], &injected),
+            Ok(())
+        );
+        assert_eq!(
+            verify_injected_cycle(&packages, &["c@1.0.0", "d@1.0.0"], &injected),
+            Err(CycleError::NotInjected {
+                package: String::from("a@1.0.0"),
+                dependent: String::from("b@1.0.0"),
+            })
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fs::File, io, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    exercise::{Exercise, Phase, Run},
+    Weight,
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub library: &'static str,
+    pub exercise: Exercise,
+    pub weight: Option<Weight>,
+    pub phase: Phase,
+    // Index of the repetition when the exercise is run multiple times.
+    pub run: usize,
+    // `None` if the library does not provide the algorithm, or does not add
+    // the vertices on their own.
+    pub duration_ns: Option<u64>,
+    pub vertices: usize,
+    pub edges: usize,
+    pub output: Option<String>,
+}
+
+impl Run {
+    pub fn records(&self, run: usize) -> impl Iterator<Item = Record> + '_ {
+        self.phases.iter().map(move |phase| Record {
+            library: self.library,
+            exercise: self.exercise,
+            weight: self.weight,
+            phase: phase.phase,
+            run,
+            duration_ns: phase.duration.map(|d| d.as_nanos() as u64),
+            vertices: self.vertices,
+            edges: self.edges,
+            output: phase.output.as_ref().map(|output| output.value()),
+        })
+    }
+}
+
+pub fn write_json(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let file = io::BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(file, records)?;
+    Ok(())
+}
+
+pub fn write_csv(path: impl AsRef<Path>, records: &[Record]) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_path(path)?;
+
+    for record in records {
+        wtr.serialize(record)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
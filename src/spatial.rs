@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::City;
+
+// Uniform grid over cities projected onto the unit sphere. Working with 3D
+// coordinates avoids special handling of the antimeridian and the poles.
+pub struct SpatialIndex<'a> {
+    cities: Vec<&'a City>,
+    cell: f64,
+    points: Vec<[f64; 3]>,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl<'a> SpatialIndex<'a> {
+    pub fn new(cities: impl IntoIterator<Item = &'a City>) -> Self {
+        let cities = cities.into_iter().collect::<Vec<_>>();
+
+        // Chord length corresponding to the maximum distance, with a margin
+        // covering the rounding errors of the distance computed in f32.
+        let angle = f64::from(City::MAX_DISTANCE) * 1.01 / f64::from(City::EARTH_RADIUS);
+        let cell = 2.0 * (angle.min(std::f64::consts::PI) * 0.5).sin();
+
+        let points = cities
+            .iter()
+            .map(|city| {
+                let lat = city.lat.to_radians();
+                let lon = city.lon.to_radians();
+                [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+            })
+            .collect::<Vec<_>>();
+
+        let mut cells: HashMap<_, Vec<_>> = HashMap::new();
+        for (i, point) in points.iter().enumerate() {
+            cells.entry(key(point, cell)).or_default().push(i);
+        }
+
+        Self {
+            cities,
+            cell,
+            points,
+            cells,
+        }
+    }
+
+    // All pairs `(i, j, distance)` with `i < j` of connected cities, indexed
+    // in the order the cities were given and ordered the same way as when
+    // scanning all pairs.
+    pub fn pairs(&self) -> Vec<(usize, usize, f32)> {
+        let mut pairs = Vec::new();
+
+        for (i, point) in self.points.iter().enumerate() {
+            let [x, y, z] = key(point, self.cell);
+
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(cell) = self.cells.get(&[x + dx, y + dy, z + dz]) else {
+                            continue;
+                        };
+
+                        for &j in cell.iter().filter(|&&j| j > i) {
+                            if let Some(d) = self.cities[i].are_connected(self.cities[j]) {
+                                pairs.push((i, j, d));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        pairs.sort_unstable_by_key(|&(i, j, _)| (i, j));
+        pairs
+    }
+}
+
+fn key(point: &[f64; 3], cell: f64) -> [i64; 3] {
+    point.map(|x| (x / cell).floor() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn city(id: u64, lat: f64, lon: f64) -> City {
+        City {
+            id,
+            name: format!("city {id}"),
+            pop: 100_000,
+            lat,
+            lon,
+        }
+    }
+
+    #[test]
+    fn pairs_match_the_scan() {
+        let cities = [
+            city(1, 50.0, 14.0),
+            city(2, 50.1, 14.5),
+            city(3, 51.0, 17.0),
+            city(4, 48.2, 16.4),
+            // Across the antimeridian.
+            city(5, 0.0, 179.9),
+            city(6, 0.0, -179.9),
+            // Across the north pole.
+            city(7, 89.5, 0.0),
+            city(8, 89.5, 180.0),
+            city(9, -33.9, 18.4),
+        ];
+
+        let pairs = SpatialIndex::new(&cities).pairs();
+        assert_eq!(pairs, crate::reference::city_pairs(&cities));
+
+        let pairs = pairs.iter().map(|&(i, j, _)| (i, j)).collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (4, 5), (6, 7)]
+        );
+    }
+
+    #[test]
+    fn pairs_at_the_maximum_distance() {
+        // Just below and above the maximum distance along the equator.
+        let degrees = |km: f64| (km / f64::from(City::EARTH_RADIUS)).to_degrees();
+        let cities = [
+            city(1, 0.0, 0.0),
+            city(2, 0.0, degrees(299.9)),
+            city(3, 0.0, -degrees(300.1)),
+            city(4, 0.0, 0.0),
+        ];
+
+        let pairs = SpatialIndex::new(&cities).pairs();
+        assert_eq!(pairs, crate::reference::city_pairs(&cities));
+        assert_eq!(
+            pairs.iter().map(|&(i, j, _)| (i, j)).collect::<Vec<_>>(),
+            [(0, 1), (1, 3)]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    // 95% confidence interval of the mean.
+    pub ci: (Duration, Duration),
+}
+
+impl Stats {
+    // Computes the statistics after removing `trim` fraction of the fastest and
+    // the same fraction of the slowest samples (outliers).
+    pub fn new(mut samples: Vec<Duration>, trim: f64) -> Option<Self> {
+        samples.sort_unstable();
+
+        let cut = (samples.len() as f64 * trim.clamp(0.0, 0.5)).floor() as usize;
+        let samples = if samples.len() > 2 * cut {
+            &samples[cut..samples.len() - cut]
+        } else {
+            &samples[..]
+        };
+
+        let n = samples.len();
+        if n == 0 {
+            return None;
+        }
+
+        let secs = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+
+        let median = if n % 2 == 0 {
+            (secs[n / 2 - 1] + secs[n / 2]) / 2.0
+        } else {
+            secs[n / 2]
+        };
+
+        let mean = secs.iter().sum::<f64>() / n as f64;
+
+        let variance = if n > 1 {
+            secs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        let std_dev = variance.sqrt();
+
+        let half_width = t_value(n - 1) * std_dev / (n as f64).sqrt();
+
+        Some(Self {
+            samples: n,
+            min: samples[0],
+            median: Duration::from_secs_f64(median),
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(std_dev),
+            ci: (
+                Duration::from_secs_f64((mean - half_width).max(0.0)),
+                Duration::from_secs_f64(mean + half_width),
+            ),
+        })
+    }
+}
+
+// Two-sided 95% quantile of Student's t-distribution.
+fn t_value(df: usize) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+
+    match df {
+        0 => 0.0,
+        df if df <= TABLE.len() => TABLE[df - 1],
+        _ => 1.960,
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{City, DIJKSTRA_START, DIJKSTRA_TARGET};
+
+// Rough outlines of land masses as ellipses `(lat, lon, lat_radius,
+// lon_radius)` in degrees. Cities are placed only inside them, apart from rare
+// islands, so that oceans remain sparse gaps in the graph.
+const LAND: &[(f32, f32, f32, f32)] = &[
+    // Africa
+    (0.0, 20.0, 36.0, 25.0),
+    (10.0, 40.0, 10.0, 12.0),
+    // Europe and western Russia
+    (50.0, 15.0, 15.0, 25.0),
+    (63.0, 40.0, 10.0, 30.0),
+    // Asia
+    (45.0, 90.0, 25.0, 50.0),
+    (22.0, 78.0, 12.0, 10.0),
+    (0.0, 115.0, 10.0, 25.0),
+    (36.0, 138.0, 6.0, 5.0),
+    // Americas
+    (45.0, -100.0, 22.0, 35.0),
+    (15.0, -90.0, 8.0, 12.0),
+    (-15.0, -60.0, 25.0, 18.0),
+    // Australia
+    (-25.0, 134.0, 12.0, 18.0),
+];
+
+// Fraction of cities placed uniformly over land instead of around hot spots.
+const RURAL: f64 = 0.25;
+// Fraction of cities placed anywhere, including oceans.
+const ISLANDS: f64 = 0.01;
+// Cities per population hot spot.
+const CITIES_PER_HOT_SPOT: usize = 250;
+
+// Generates `count` cities deterministically for given seed. Cities cluster
+// around hot spots, larger hot spots having more and bigger cities. The start
+// and target cities of the shortest paths exercise are always included (if
+// `count` allows), at their real positions. All populations pass the filter of
+// `load_cities`.
+pub fn cities(count: usize, seed: u64) -> Vec<City> {
+    let mut rng = Rng::new(seed);
+
+    let hot_spots = (0..(count / CITIES_PER_HOT_SPOT).max(8))
+        .map(|_| {
+            let (lat, lon) = rng.land();
+            // Pareto-distributed size, so that there are few large hot spots.
+            let size = rng.pareto(1.2).min(50.0);
+            (lat, lon, size)
+        })
+        .collect::<Vec<_>>();
+    let cumulative = hot_spots
+        .iter()
+        .scan(0.0, |total, (_, _, size)| {
+            *total += size;
+            Some(*total)
+        })
+        .collect::<Vec<_>>();
+    let total_size = *cumulative.last().unwrap();
+
+    let anchors = [
+        (DIJKSTRA_START, 3_433_441, -33.92584, 18.42322),
+        (DIJKSTRA_TARGET, 319_263, 68.97917, 33.09251),
+    ];
+
+    let mut cities = anchors
+        .into_iter()
+        .take(count)
+        .enumerate()
+        .map(|(i, (name, pop, lat, lon))| City {
+            id: i as u64 + 1,
+            name: name.to_string(),
+            pop,
+            lat,
+            lon,
+        })
+        .collect::<Vec<_>>();
+
+    while cities.len() < count {
+        let p = rng.next_f64();
+        let (lat, lon, size) = if p < ISLANDS {
+            let (lat, lon) = rng.anywhere();
+            (lat, lon, 1.0)
+        } else if p < ISLANDS + RURAL {
+            let (lat, lon) = rng.land();
+            (lat, lon, 1.0)
+        } else {
+            let pick = rng.next_f64() * total_size;
+            let index = cumulative.partition_point(|&total| total <= pick);
+            let (lat, lon, size) = hot_spots[index.min(hot_spots.len() - 1)];
+
+            // Bigger hot spots spread further.
+            let spread = 0.5 + size.sqrt();
+            let lat = lat + (rng.normal() * spread) as f32;
+            let lon = lon + (rng.normal() * spread) as f32 / lat.to_radians().cos().max(0.2);
+
+            if !on_land(lat, lon) {
+                continue;
+            }
+
+            (lat, lon, size)
+        };
+
+        let pop = (25_001.0 * rng.pareto(1.5) * size.sqrt()).min(40_000_000.0) as u64;
+
+        cities.push(City {
+            id: cities.len() as u64 + 1,
+            name: format!("Synthetic {}", cities.len() + 1),
+            pop,
+            lat: lat.clamp(-89.9, 89.9).into(),
+            lon: wrap_lon(lon).into(),
+        });
+    }
+
+    cities
+}
+
+// Draws `count` pairs `(start, target)` of distinct indices into `n` cities
+// deterministically for given seed. Empty if there are fewer than two cities.
+pub fn pairs(n: usize, count: usize, seed: u64) -> Vec<(usize, usize)> {
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let mut rng = Rng::new(seed);
+    let mut pairs = Vec::with_capacity(count);
+
+    while pairs.len() < count {
+        let start = (rng.next_u64() % n as u64) as usize;
+        let target = (rng.next_u64() % n as u64) as usize;
+
+        if start != target {
+            pairs.push((start, target));
+        }
+    }
+
+    pairs
+}
+
+fn on_land(lat: f32, lon: f32) -> bool {
+    LAND.iter().any(|&(clat, clon, rlat, rlon)| {
+        let dlat = (lat - clat) / rlat;
+        let dlon = wrap_lon(lon - clon) / rlon;
+        dlat * dlat + dlon * dlon <= 1.0
+    })
+}
+
+fn wrap_lon(lon: f32) -> f32 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+// SplitMix64. Implemented here rather than using an external crate so that the
+// generated cities never change with a dependency update.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Pareto distribution with minimum 1.
+    fn pareto(&mut self, alpha: f64) -> f64 {
+        (1.0 - self.next_f64()).powf(-1.0 / alpha)
+    }
+
+    // Standard normal distribution (Box-Muller).
+    fn normal(&mut self) -> f64 {
+        let u = 1.0 - self.next_f64();
+        let v = self.next_f64();
+        (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+    }
+
+    // Uniform over the sphere.
+    fn anywhere(&mut self) -> (f32, f32) {
+        let lat = (2.0 * self.next_f64() - 1.0).asin().to_degrees();
+        let lon = 360.0 * self.next_f64() - 180.0;
+        (lat as f32, lon as f32)
+    }
+
+    // Uniform over land.
+    fn land(&mut self) -> (f32, f32) {
+        loop {
+            let (lat, lon) = self.anywhere();
+            if on_land(lat, lon) {
+                return (lat, lon);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cities_are_deterministic() {
+        let cities = super::cities(500, 7);
+
+        assert_eq!(cities, super::cities(500, 7));
+        assert_ne!(cities, super::cities(500, 8));
+
+        assert_eq!(cities.len(), 500);
+        assert_eq!(cities[0].name, DIJKSTRA_START);
+        assert_eq!(cities[1].name, DIJKSTRA_TARGET);
+        assert!(cities
+            .iter()
+            .enumerate()
+            .all(|(i, city)| city.id == i as u64 + 1));
+        assert!(cities.iter().all(|city| city.pop > 25_000
+            && (-90.0..=90.0).contains(&city.lat)
+            && (-180.0..=180.0).contains(&city.lon)));
+
+        assert_eq!(super::cities(1, 7).len(), 1);
+    }
+
+    #[test]
+    fn pairs_are_deterministic() {
+        let pairs = super::pairs(10, 50, 3);
+
+        assert_eq!(pairs, super::pairs(10, 50, 3));
+        assert_ne!(pairs, super::pairs(10, 50, 4));
+
+        assert_eq!(pairs.len(), 50);
+        assert!(pairs.iter().all(|&(s, t)| s != t && s < 10 && t < 10));
+
+        assert!(super::pairs(1, 5, 3).is_empty());
+    }
+}
@@ -1,106 +1,527 @@
 use std::{
     cmp::Ordering,
-    ops::{Add, Div, Mul, Rem, Sub},
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign},
+    str::FromStr,
 };
 
-use num_traits::{One, Zero};
+use num_traits::{
+    Bounded, CheckedSub, Float, Num, NumCast, One, ParseFloatError, SaturatingSub, ToPrimitive,
+    Zero,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 // OrderedFloat does not implement num_traits::Unsigned
+//
+// The value is always finite and non-negative, with negative zero normalized to
+// positive zero. Like integer overflow, an arithmetic result breaking that is a
+// bug and panics, also in release builds. Unreachable vertices are represented
+// outside the value, as prepona does with `Magnitude`.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct UF32(f32);
+pub struct UFloat<T>(T);
+
+pub type UF32 = UFloat<f32>;
+pub type UF64 = UFloat<f64>;
+
+// Floats that can be wrapped, that is, `f32` and `f64`.
+pub trait Primitive:
+    Float + Num<FromStrRadixErr = ParseFloatError> + Default + fmt::Debug + fmt::Display
+{
+    fn total_cmp(&self, other: &Self) -> Ordering;
+    fn to_bits(self) -> u64;
+}
+
+impl Primitive for f32 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+
+    fn to_bits(self) -> u64 {
+        f32::to_bits(self).into()
+    }
+}
+
+impl Primitive for f64 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+
+    fn to_bits(self) -> u64 {
+        f64::to_bits(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UFloatError<T> {
+    NaN,
+    Infinite(T),
+    Negative(T),
+    // A finite value too large for `f32`.
+    Overflow,
+}
+
+pub type UF32Error = UFloatError<f32>;
+pub type UF64Error = UFloatError<f64>;
+
+impl<T: fmt::Display> fmt::Display for UFloatError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UFloatError::NaN => write!(f, "unsigned float cannot be NaN"),
+            UFloatError::Infinite(x) => write!(f, "unsigned float cannot be infinite, got {x}"),
+            UFloatError::Negative(x) => write!(f, "unsigned float cannot be negative, got {x}"),
+            UFloatError::Overflow => write!(f, "unsigned float is too large for f32"),
+        }
+    }
+}
 
-impl UF32 {
-    pub fn new(x: f32) -> Option<Self> {
-        x.is_sign_positive().then_some(Self(x))
+impl<T: fmt::Debug + fmt::Display> std::error::Error for UFloatError<T> {}
+
+#[derive(Debug)]
+pub enum ParseUFloatError<T> {
+    Float(ParseFloatError),
+    Invalid(UFloatError<T>),
+}
+
+pub type ParseUF32Error = ParseUFloatError<f32>;
+pub type ParseUF64Error = ParseUFloatError<f64>;
+
+impl<T: fmt::Display> fmt::Display for ParseUFloatError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseUFloatError::Float(error) => write!(f, "invalid float: {error}"),
+            ParseUFloatError::Invalid(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> std::error::Error for ParseUFloatError<T> {}
+
+impl<T: Primitive> UFloat<T> {
+    pub fn new(x: T) -> Option<Self> {
+        Self::validate(x).ok()
     }
 
-    pub fn get(self) -> f32 {
+    pub fn get(self) -> T {
         self.0
     }
+
+    // `None` if the result would be negative.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        (self.0 >= rhs.0).then(|| Self::result(self.0 - rhs.0))
+    }
+
+    // Zero if the result would be negative.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_default()
+    }
+
+    // Wraps a result of an arithmetic operation.
+    fn result(x: T) -> Self {
+        Self::validate(x).unwrap_or_else(|error| panic!("invalid arithmetic result: {error}"))
+    }
+
+    fn validate(x: T) -> Result<Self, UFloatError<T>> {
+        if x.is_nan() {
+            Err(UFloatError::NaN)
+        } else if x.is_infinite() {
+            Err(UFloatError::Infinite(x))
+        } else if x < T::zero() {
+            Err(UFloatError::Negative(x))
+        } else {
+            Ok(Self(x + T::zero()))
+        }
+    }
 }
 
-impl PartialOrd for UF32 {
+// A generic impl would conflict with the blanket `TryFrom<U> for T`.
+macro_rules! impl_try_from {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<$t> for UFloat<$t> {
+                type Error = UFloatError<$t>;
+
+                fn try_from(x: $t) -> Result<Self, Self::Error> {
+                    Self::validate(x)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from!(f32, f64);
+
+// Widening is exact.
+impl From<UF32> for UF64 {
+    fn from(x: UF32) -> Self {
+        UFloat(x.0.into())
+    }
+}
+
+// Fails if the value is too large for `f32`. Otherwise rounds to the nearest
+// `f32`, which keeps it non-negative.
+impl TryFrom<UF64> for UF32 {
+    type Error = UF32Error;
+
+    fn try_from(x: UF64) -> Result<Self, Self::Error> {
+        UF32::try_from(x.0 as f32).map_err(|_| UFloatError::Overflow)
+    }
+}
+
+impl From<UF32> for f32 {
+    fn from(x: UF32) -> Self {
+        x.0
+    }
+}
+
+impl From<UF32> for f64 {
+    fn from(x: UF32) -> Self {
+        x.0.into()
+    }
+}
+
+impl From<UF64> for f64 {
+    fn from(x: UF64) -> Self {
+        x.0
+    }
+}
+
+impl<T: Primitive> PartialOrd for UFloat<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(self.cmp(other))
     }
 }
 
-impl PartialEq for UF32 {
+impl<T: Primitive> PartialEq for UFloat<T> {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
     }
 }
 
-impl Ord for UF32 {
+impl<T: Primitive> Ord for UFloat<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.0.total_cmp(&other.0)
     }
 }
 
-impl Eq for UF32 {}
+impl<T: Primitive> Eq for UFloat<T> {}
 
-impl One for UF32 {
+// Consistent with equality thanks to the normalized zero.
+impl<T: Primitive> Hash for UFloat<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for UFloat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: Primitive> FromStr for UFloat<T> {
+    type Err = ParseUFloatError<T>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as Num>::from_str_radix(s, 10)
+    }
+}
+
+impl<T: Primitive> One for UFloat<T> {
     fn one() -> Self {
-        UF32(1.0)
+        UFloat(T::one())
     }
 }
 
-impl Zero for UF32 {
+impl<T: Primitive> Zero for UFloat<T> {
     fn zero() -> Self {
-        UF32(0.0)
+        UFloat(T::zero())
     }
 
     fn is_zero(&self) -> bool {
-        self.0 == 0.0
+        self.0.is_zero()
     }
 }
 
-impl Add for UF32 {
-    type Output = UF32;
+impl<T: Primitive> Add for UFloat<T> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        UF32(self.0 + rhs.0)
+        Self::result(self.0 + rhs.0)
     }
 }
 
-impl Mul for UF32 {
-    type Output = UF32;
+impl<T: Primitive> Mul for UFloat<T> {
+    type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        UF32(self.0 * rhs.0)
+        Self::result(self.0 * rhs.0)
     }
 }
 
-impl Sub for UF32 {
-    type Output = UF32;
+impl<T: Primitive> Sub for UFloat<T> {
+    type Output = Self;
 
+    // Panics if the result would be negative, like for unsigned integers.
     fn sub(self, rhs: Self) -> Self::Output {
-        UF32(self.0 - rhs.0)
+        Self::result(self.0 - rhs.0)
     }
 }
 
-impl Div for UF32 {
-    type Output = UF32;
+impl<T: Primitive> Div for UFloat<T> {
+    type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
-        UF32(self.0 / rhs.0)
+        Self::result(self.0 / rhs.0)
     }
 }
 
-impl Rem for UF32 {
-    type Output = UF32;
+impl<T: Primitive> Rem for UFloat<T> {
+    type Output = Self;
 
     fn rem(self, rhs: Self) -> Self::Output {
-        UF32(self.0 % rhs.0)
+        Self::result(self.0 % rhs.0)
+    }
+}
+
+impl<T: Primitive> AddAssign for UFloat<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Primitive> SubAssign for UFloat<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Primitive> MulAssign for UFloat<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Primitive> DivAssign for UFloat<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: Primitive> RemAssign for UFloat<T> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl<T: Primitive> Sum for UFloat<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
     }
 }
 
-impl num_traits::Num for UF32 {
-    type FromStrRadixErr = <f32 as num_traits::Num>::FromStrRadixErr;
+impl<'a, T: Primitive> Sum<&'a UFloat<T>> for UFloat<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: Primitive> Num for UFloat<T> {
+    type FromStrRadixErr = ParseUFloatError<T>;
 
     fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
-        f32::from_str_radix(str, radix).map(UF32)
+        let x = T::from_str_radix(str, radix).map_err(ParseUFloatError::Float)?;
+        Self::validate(x).map_err(ParseUFloatError::Invalid)
+    }
+}
+
+impl<T: Primitive> num_traits::Unsigned for UFloat<T> {}
+
+impl<T: Primitive> Bounded for UFloat<T> {
+    fn min_value() -> Self {
+        UFloat(T::zero())
+    }
+
+    fn max_value() -> Self {
+        UFloat(T::max_value())
+    }
+}
+
+impl<T: Primitive> ToPrimitive for UFloat<T> {
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    fn to_f32(&self) -> Option<f32> {
+        self.0.to_f32()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.0.to_f64()
+    }
+}
+
+impl<T: Primitive> NumCast for UFloat<T> {
+    fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+        <T as NumCast>::from(n).and_then(Self::new)
+    }
+}
+
+impl<T: Primitive> CheckedSub for UFloat<T> {
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        UFloat::checked_sub(*self, *rhs)
+    }
+}
+
+impl<T: Primitive> SaturatingSub for UFloat<T> {
+    fn saturating_sub(&self, rhs: &Self) -> Self {
+        UFloat::saturating_sub(*self, *rhs)
+    }
+}
+
+impl<T: Serialize> Serialize for UFloat<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
     }
 }
 
-impl num_traits::Unsigned for UF32 {}
+impl<'de, T: Primitive + Deserialize<'de>> Deserialize<'de> for UFloat<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let x = T::deserialize(deserializer)?;
+        Self::validate(x).map_err(de::Error::custom)
+    }
+}
+
+// The weight bounds of the libraries that are not covered by the standard
+// traits. Being finite, the largest value stands for infinity, as it does for
+// integers.
+impl<T: Primitive> gryf::core::weight::Weight for UFloat<T> {
+    type Ord = Self;
+
+    fn zero() -> Self {
+        UFloat(T::zero())
+    }
+
+    fn inf() -> Self {
+        UFloat(T::max_value())
+    }
+
+    fn is_unsigned() -> bool {
+        true
+    }
+}
+
+impl<T: Primitive> petgraph::algo::FloatMeasure for UFloat<T> {
+    fn zero() -> Self {
+        UFloat(T::zero())
+    }
+
+    fn infinite() -> Self {
+        UFloat(T::max_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_rejects_negative_infinite_and_nan() {
+        assert_eq!(UF32::try_from(-1.0), Err(UFloatError::Negative(-1.0)));
+        assert_eq!(
+            UF32::try_from(f32::INFINITY),
+            Err(UFloatError::Infinite(f32::INFINITY))
+        );
+        assert_eq!(
+            UF64::try_from(f64::NEG_INFINITY),
+            Err(UFloatError::Infinite(f64::NEG_INFINITY))
+        );
+        assert_eq!(UF32::try_from(f32::NAN), Err(UFloatError::NaN));
+        assert_eq!(UF64::try_from(f64::NAN), Err(UFloatError::NaN));
+    }
+
+    #[test]
+    fn try_from_normalizes_negative_zero() {
+        let zero = UF32::try_from(-0.0).unwrap();
+
+        assert!(zero.get().is_sign_positive());
+        assert_eq!(zero, UF32::zero());
+        assert_eq!(zero.cmp(&UF32::zero()), Ordering::Equal);
+    }
+
+    #[test]
+    fn largest_value_stands_for_infinity() {
+        let max = UF32::try_from(f32::MAX).unwrap();
+
+        assert_eq!(<UF32 as petgraph::algo::FloatMeasure>::infinite(), max);
+        assert_eq!(<UF32 as gryf::core::weight::Weight>::inf(), max);
+        assert_eq!(UF32::max_value(), max);
+    }
+
+    #[test]
+    fn sub() {
+        let one = UF64::one();
+        let two = one + one;
+
+        assert_eq!(two - one, one);
+        assert_eq!(one.checked_sub(two), None);
+        assert_eq!(one.saturating_sub(two), UF64::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn negative_result_panics() {
+        let _ = UF64::one() - UF64::max_value();
+    }
+
+    #[test]
+    #[should_panic(expected = "infinite")]
+    fn overflow_panics() {
+        let _ = UF32::max_value() + UF32::max_value();
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN")]
+    fn nan_result_panics() {
+        let _ = UF32::zero() / UF32::zero();
+    }
+
+    #[test]
+    fn widening() {
+        let x = UF32::new(0.1).unwrap();
+        let wide: UF64 = x.into();
+        let (x32, x64): (f32, f64) = (x.into(), x.into());
+
+        assert_eq!(wide.get(), 0.1f32 as f64);
+        assert_eq!(x32, 0.1);
+        assert_eq!(x64, 0.1f32 as f64);
+        assert_eq!(UF32::new(-0.1), None);
+    }
+
+    #[test]
+    fn narrowing() {
+        let x = UF64::try_from(0.1).unwrap();
+        assert_eq!(UF32::try_from(x).unwrap().get(), 0.1f32);
+
+        let large = UF64::try_from(f64::MAX).unwrap();
+        assert_eq!(UF32::try_from(large), Err(UFloatError::Overflow));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("2.5".parse::<UF32>().unwrap().get(), 2.5);
+        assert!(matches!(
+            "-2.5".parse::<UF32>(),
+            Err(ParseUFloatError::Invalid(UFloatError::Negative(_)))
+        ));
+        assert!(matches!(
+            "x".parse::<UF32>(),
+            Err(ParseUFloatError::Float(_))
+        ));
+    }
+}